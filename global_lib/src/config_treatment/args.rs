@@ -5,13 +5,13 @@ use super::{
     result_fields::{DebitCurves, ResultDuration},
     subargs::SubArgs,
     utils::{
-        extract_serde_arr, extract_serde_obj, extract_serde_string, serde_n_to_u16,
+        extract_crash, extract_serde_arr, extract_serde_obj, extract_serde_string, serde_n_to_u16,
         serde_n_to_usize, JsonMap, JsonValue,
     },
 };
 use crate::{
-    config_treatment::plot::PlotCurve, dec, enc, messages::Algo, settings::WARM_UP, Evaluation,
-    KindEvaluation, Step,
+    config_treatment::plot::PlotCurve, dec, enc, messages::Algo, settings::WARM_UP, ByzBehaviour,
+    Evaluation, KindEvaluation, Step,
 };
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
@@ -79,8 +79,10 @@ impl Args {
         };
         let eval = eval.to_eval(if current_arg.has_sharing() {
             Step::Sharing
-        } else {
+        } else if current_arg.has_reconstruct() {
             Step::Reconstruct
+        } else {
+            current_arg.first_step()
         });
         current_arg.set_eval(eval);
         Ok(())
@@ -189,6 +191,27 @@ impl Args {
     }
 
     /// Simply evolving the latency, takes as parameter the computed results and call generic evolve with good parameters
+    /// Records the number of nodes which produced an output in the last operation
+    pub fn record_outputs(&mut self, outputs: u16) {
+        self.current_arg_mut()
+            .expect("Config is over !")
+            .record_outputs(outputs)
+    }
+
+    /// Records the most rounds taken by a binary agreement in the last operation, if it ran any
+    pub fn record_rounds(&mut self, rounds: Option<u16>) {
+        self.current_arg_mut()
+            .expect("Config is over !")
+            .record_rounds(rounds)
+    }
+
+    /// Records whether the last operation timed out before the nodes output
+    pub fn record_timeout(&mut self, timed_out: bool) {
+        self.current_arg_mut()
+            .expect("Config is over !")
+            .record_timeout(timed_out)
+    }
+
     pub fn latency_evolve(&mut self, result: ResultDuration) -> anyhow::Result<()> {
         assert!(!self.is_over());
         let data_result = self.data_result.clone();
@@ -318,6 +341,16 @@ impl Args {
                             .map(|algo| Algo::from(extract_serde_string(algo) as &str))
                             .collect(),
                     ),
+                    "weights" => subarg.set_weights(arr.iter().map(serde_n_to_u16).collect()),
+                    "crashes" => subarg.set_crashes(arr.iter().map(extract_crash).collect()),
+                    "partitions" => subarg.set_partitions(
+                        serde_json::from_value(value.clone()).expect("Invalid partitions"),
+                    ),
+                    "byz_behaviours" => subarg.set_byz_behaviours(
+                        arr.iter()
+                            .map(|b| ByzBehaviour::from(extract_serde_string(b) as &str))
+                            .collect(),
+                    ),
                     _ => {
                         variations.push((
                             TypeField::from(key as &str),
@@ -325,10 +358,32 @@ impl Args {
                         ));
                     }
                 },
-                JsonValue::String(s) => {
-                    assert!(key == "main", "Invalid arg for a state setup: {key}");
-                    main = Some(TypeField::from(s as &str))
-                }
+                JsonValue::String(s) => match key as &str {
+                    "main" => main = Some(TypeField::from(s as &str)),
+                    "secret" => subarg.set_secret(s.as_bytes().to_vec()),
+                    "byz_behaviour" => {
+                        subarg.set_byz_behaviours(vec![ByzBehaviour::from(s as &str)])
+                    }
+                    _ => panic!("Invalid arg for a state setup: {key}"),
+                },
+                JsonValue::Object(_) => match key as &str {
+                    "faults" => subarg.set_faults(
+                        serde_json::from_value(value.clone()).expect("Invalid network faults"),
+                    ),
+                    "dealer_attacks" => subarg.set_dealer_attacks(
+                        extract_serde_obj(value)
+                            .iter()
+                            .map(|(algo, attack)| {
+                                (
+                                    Algo::from(algo as &str),
+                                    serde_json::from_value(attack.clone())
+                                        .expect("Invalid dealer attack"),
+                                )
+                            })
+                            .collect(),
+                    ),
+                    _ => panic!("Invalid arg for a state setup: {key}"),
+                },
                 _ => panic!("Invalid arg for a state setup: {key}"),
             }
         }
@@ -377,8 +432,9 @@ impl Args {
         }
     }
 
-    /// This function will load a configuration from a file path. If the path or the configuration is invalid the function fails.
-    pub fn from_file(path: String) -> Self {
+    /// This function will load a configuration from a file path. If the path or the configuration is invalid the function fails,
+    /// a setup asking options its algos and steps don't support is returned as an error.
+    pub fn from_file(path: String) -> anyhow::Result<Self> {
         let mut res = Args::default();
         let content = read_to_string(path).expect("Path invalid");
         let value: JsonValue = from_str(&content).expect("The given json file is invalid");
@@ -396,10 +452,13 @@ impl Args {
                 }
             }
             assert!(subarg.has_name());
+            subarg
+                .check()
+                .with_context(|| format!("Invalid setup {i}"))?;
             res.args.push(subarg);
         }
         res.init();
-        res
+        Ok(res)
     }
 
     /// Returns true if the current state asking reconstruction step
@@ -1,5 +1,13 @@
-use crate::{as_number, messages::Algo, Evaluation, KindEvaluation, Step};
+use crate::{
+    as_number,
+    messages::Algo,
+    network::{NetworkFaults, Partition},
+    settings::OP_TIMEOUT,
+    ByzBehaviour, CrashPoint, DealerAttack, Evaluation, KindEvaluation, Step,
+};
+use anyhow::bail;
 use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
 use tokio::time::Duration;
 
 use std::fmt::{Display, Error as FmtErr, Formatter};
@@ -13,16 +21,38 @@ as_number!(
         TDenom,
         LDenom,
         NbByz,
+        Dealers,
+        NewN,
+        NewTDenom,
+        NewOffset,
+        Packing,
+        PrivateReconstruct,
+        ByzDelay,
+        OpTimeout,
         T,
         L,
     },
     derive(Debug, Eq, Copy, Clone, PartialEq, Deserialize, Serialize)
 );
 
-pub const NB_FIELDS: usize = 6;
+pub const NB_FIELDS: usize = 14;
 pub const TO_DISPLAY: usize = 4;
-pub static STATIC_TYPE_FIELD: [&str; NB_FIELDS] =
-    ["n", "dealer_corruption", "batch_size", "t", "l", "nb_byz"];
+pub static STATIC_TYPE_FIELD: [&str; NB_FIELDS] = [
+    "n",
+    "dealer_corruption",
+    "batch_size",
+    "t",
+    "l",
+    "nb_byz",
+    "dealers",
+    "new_n",
+    "new_t",
+    "new_offset",
+    "packing",
+    "private_reconstruct",
+    "byz_delay",
+    "op_timeout",
+];
 
 impl Display for TypeField {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtErr> {
@@ -52,6 +82,11 @@ impl TypeField {
             TypeField::NbByz => "Number of Byzantin node in the network",
             TypeField::TDenom => "Threshold",
             TypeField::LDenom => "Second Threshold",
+            TypeField::Dealers => "Number of concurrent dealers",
+            TypeField::NewN => "Size of the new committee",
+            TypeField::NewTDenom => "Threshold of the new committee",
+            TypeField::Packing => "Secrets per polynomial",
+            TypeField::ByzDelay => "Delay of the byzantine nodes (ms)",
             _ => panic!("Field {self} is not allowed on an x axe"),
         }
     }
@@ -63,6 +98,15 @@ pub struct Fields {
     algo: Algo,
     base_latency: Option<Duration>,
     eval: Evaluation,
+    step_input: Option<Vec<u8>>,
+    weights: Option<Vec<u16>>,
+    secret: Option<Vec<u8>>,
+    byz_behaviours: Option<Vec<ByzBehaviour>>,
+    crashes: Option<Vec<(u16, CrashPoint)>>,
+    faults: Option<NetworkFaults>,
+    partitions: Option<Vec<Partition>>,
+    dealer_attacks: Option<Vec<(Algo, DealerAttack)>>,
+    op_start: Option<SystemTime>,
 }
 
 impl Fields {
@@ -72,17 +116,37 @@ impl Fields {
             algo: Algo::default(),
             base_latency: None,
             eval: Evaluation::default(),
+            step_input: None,
+            weights: None,
+            secret: None,
+            byz_behaviours: None,
+            crashes: None,
+            faults: None,
+            partitions: None,
+            dealer_attacks: None,
+            op_start: None,
         };
         res.set(TypeField::BatchSize, 1);
+        res.set(TypeField::Dealers, 1);
+        res.set(TypeField::Packing, 1);
         res
     }
 
     pub fn warm_up(n: u16) -> Self {
         Self {
-            fields: vec![n, 0, 3, 33, 0, 0],
+            fields: vec![n, 0, 3, 33, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0],
             algo: Algo::AvssSimpl,
             base_latency: None,
             eval: Evaluation::Latency(Step::Sharing),
+            step_input: None,
+            weights: None,
+            secret: None,
+            byz_behaviours: None,
+            crashes: None,
+            faults: None,
+            partitions: None,
+            dealer_attacks: None,
+            op_start: None,
         }
     }
 
@@ -102,16 +166,164 @@ impl Fields {
         self.eval = eval
     }
 
+    pub fn set_step_input(&mut self, input: Vec<u8>) {
+        self.step_input = Some(input)
+    }
+
+    pub fn step_input(&self) -> &[u8] {
+        self.step_input.as_ref().expect("No input for the step")
+    }
+
+    /// Payload shared by the dealer in place of random secrets
+    pub fn set_secret(&mut self, secret: Vec<u8>) {
+        self.secret = Some(secret)
+    }
+
+    pub fn secret(&self) -> Option<&[u8]> {
+        self.secret.as_deref()
+    }
+
+    /// Behaviours of the byzantine nodes, the i-th one is given to the i-th byzantine node, looping
+    /// over them when there are less behaviours than byzantine nodes
+    pub fn set_byz_behaviours(&mut self, behaviours: Vec<ByzBehaviour>) {
+        assert!(!behaviours.is_empty(), "No byzantine behaviour given");
+        self.byz_behaviours = Some(behaviours)
+    }
+
+    /// Behaviour of the node i, None when it is honest
+    pub fn byz_behaviour(&self, i: u16) -> Option<ByzBehaviour> {
+        (i < self.nb_byz()).then(|| {
+            self.byz_behaviours
+                .as_ref()
+                .map_or(ByzBehaviour::default(), |b| b[i as usize % b.len()])
+        })
+    }
+
+    /// Nodes crashing during the operation, with the point where they do
+    pub fn set_crashes(&mut self, crashes: Vec<(u16, CrashPoint)>) {
+        self.crashes = Some(crashes)
+    }
+
+    pub fn remove_crashes(&mut self) {
+        self.crashes = None
+    }
+
+    pub fn crash_point(&self, i: u16) -> Option<CrashPoint> {
+        self.crashes
+            .as_ref()?
+            .iter()
+            .find(|(node, _)| *node == i)
+            .map(|(_, point)| *point)
+    }
+
+    pub fn set_faults(&mut self, faults: NetworkFaults) {
+        faults.check();
+        self.faults = Some(faults)
+    }
+
+    /// Faults injected on the links between the nodes, None for a reliable network
+    pub fn faults(&self) -> Option<&NetworkFaults> {
+        self.faults.as_ref()
+    }
+
+    pub fn set_partitions(&mut self, partitions: Vec<Partition>) {
+        assert!(
+            partitions.iter().all(|p| p.from < p.to),
+            "A partition must heal after it begins"
+        );
+        self.partitions = Some(partitions)
+    }
+
+    pub fn partitions(&self) -> Option<&[Partition]> {
+        self.partitions.as_deref()
+    }
+
+    /// Time the interface started the operation at, the partitions are timed from it
+    pub fn set_op_start(&mut self, start: SystemTime) {
+        self.op_start = Some(start)
+    }
+
+    pub fn op_start(&self) -> Option<SystemTime> {
+        self.op_start
+    }
+
+    /// Attack of the dealer for each algo, the algos left out keep an honest dealer
+    pub fn set_dealer_attacks(&mut self, attacks: Vec<(Algo, DealerAttack)>) {
+        for (algo, attack) in &attacks {
+            assert!(
+                attack.supported_by(*algo),
+                "The dealer of {algo:?} can't run the attack {attack:?}"
+            );
+        }
+        self.dealer_attacks = Some(attacks)
+    }
+
+    pub fn dealer_attacks(&self) -> Option<&[(Algo, DealerAttack)]> {
+        self.dealer_attacks.as_deref()
+    }
+
+    /// Attack of the dealer of the current algo, None when it is honest
+    pub fn dealer_attack(&self) -> Option<&DealerAttack> {
+        self.dealer_attacks()?
+            .iter()
+            .find(|(algo, _)| *algo == self.algo)
+            .map(|(_, attack)| attack)
+    }
+
+    pub fn set_weights(&mut self, weights: Vec<u16>) {
+        self.weights = Some(weights)
+    }
+
+    /// Number of evaluation points held by the node i, 1 when the weights are unset. The weights
+    /// are checked to cover every node.
+    pub fn weight(&self, i: u16) -> u16 {
+        self.weights.as_ref().map_or(1, |w| w[i as usize])
+    }
+
+    /// Sum of the weights of the n nodes, n when the weights are unset
+    pub fn total_weight(&self) -> u16 {
+        (0..self.n()).map(|i| self.weight(i)).sum()
+    }
+
+    /// Threshold counted in evaluation points rather than in nodes
+    pub fn weighted_t(&self) -> u16 {
+        self.adjust(self.total_weight(), TypeField::TDenom)
+    }
+
+    /// Checks the options only some algos and steps support, the nodes can't run the others
+    pub fn check(&self) -> anyhow::Result<()> {
+        if let Some(weights) = &self.weights {
+            if self.algo != Algo::Feldman
+                || !matches!(self.step(), Step::Sharing | Step::Reconstruct)
+            {
+                bail!("Weights are only supported by the feldman sharing and reconstruction");
+            }
+            if weights.len() != self.n() as usize {
+                bail!("{} weights are given for {} nodes", weights.len(), self.n());
+            }
+            if weights.contains(&0) {
+                bail!("A node weight can't be 0");
+            }
+        }
+        if self.private_reconstruct() && self.algo != Algo::Feldman {
+            bail!("Private reconstruction is only supported by feldman");
+        }
+        if self.step() == Step::Triples && self.batch_size() < 2 {
+            bail!("A batch of at least 2 secrets is needed for a triple");
+        }
+        Ok(())
+    }
+
     pub fn label_format(kind: &str, val: u16) -> String {
         format!(
             "{kind}: {}",
-            if kind == "dealer_corruption" {
+            if kind == "dealer_corruption" || kind == "private_reconstruct" {
                 (val == 1).to_string()
             } else {
                 format!(
                     "{val}{}",
                     match kind {
-                        "t" | "l" => " %",
+                        "t" | "l" | "new_t" => " %",
                         _ => "",
                     }
                 )
@@ -177,10 +389,101 @@ impl Fields {
         self.get(TypeField::NbByz)
     }
 
+    /// Delay of the messages of a delaying byzantine node in ms, 100 when unset
+    pub fn byz_delay(&self) -> u16 {
+        match self.get(TypeField::ByzDelay) {
+            0 => 100,
+            delay => delay,
+        }
+    }
+
+    /// Time the interface waits for the outputs of an operation before giving up on it, in s
+    pub fn op_timeout(&self) -> Duration {
+        match self.get(TypeField::OpTimeout) {
+            0 => OP_TIMEOUT,
+            timeout => Duration::from_secs(timeout as u64),
+        }
+    }
+
     pub fn batch_size(&self) -> u16 {
         self.get(TypeField::BatchSize)
     }
 
+    pub fn dealers(&self) -> u16 {
+        self.get(TypeField::Dealers).max(1)
+    }
+
+    /// Whether every node deals a session and the nodes agree through an ACS on the sessions summed
+    /// into the output, as in a refresh, a distributed key generation or a beacon epoch
+    pub fn agrees_on_sessions(&self) -> bool {
+        matches!(self.step(), Step::Refresh | Step::Beacon)
+            || (self.algo() == Algo::Adkg && self.step() == Step::Sharing)
+    }
+
+    /// Whether the operation runs binary agreements, whose coins need a key dealt beforehand. The
+    /// source committee of a resharing and the nodes making triples agree on the dealings they
+    /// combine.
+    pub fn uses_aba(&self) -> bool {
+        self.algo().uses_aba()
+            || self.agrees_on_sessions()
+            || matches!(self.step(), Step::Reshare | Step::Triples)
+    }
+
+    /// Number of secrets embedded in a single polynomial, 1 when the secrets are not packed
+    pub fn packing(&self) -> u16 {
+        self.get(TypeField::Packing).max(1)
+    }
+
+    /// Whether the shares are reconstructed by the interface alone rather than by every node
+    pub fn private_reconstruct(&self) -> bool {
+        self.get(TypeField::PrivateReconstruct) == 1
+    }
+
+    /// Size of the committee receiving a resharing, the current one when unset
+    pub fn new_n(&self) -> u16 {
+        match self.get(TypeField::NewN) {
+            0 => self.n(),
+            new_n => new_n,
+        }
+    }
+
+    pub fn new_t(&self) -> u16 {
+        let denom = match self.get(TypeField::NewTDenom) {
+            0 => TypeField::TDenom,
+            _ => TypeField::NewTDenom,
+        };
+        self.adjust(self.new_n(), denom)
+    }
+
+    pub fn new_t_denom(&self) -> u16 {
+        match self.get(TypeField::NewTDenom) {
+            0 => self.get(TypeField::TDenom),
+            t => t,
+        }
+    }
+
+    /// Index of the first node of the new committee
+    pub fn new_offset(&self) -> u16 {
+        self.get(TypeField::NewOffset)
+    }
+
+    /// Number of nodes taking part in the operation, both committees for a resharing
+    pub fn nodes_involved(&self) -> u16 {
+        match self.step() {
+            Step::Reshare => self.n().max(self.new_offset() + self.new_n()),
+            _ => self.n(),
+        }
+    }
+
+    /// Number of results of an operation counted in throughput mode: the secrets of the batch, or
+    /// the triples made from its two halves
+    pub fn outputs_per_operation(&self) -> u16 {
+        match self.step() {
+            Step::Triples => self.batch_size() / 2,
+            _ => self.batch_size(),
+        }
+    }
+
     pub fn algo(&self) -> Algo {
         self.algo
     }
@@ -7,7 +7,8 @@ use super::{
 use crate::{
     config_treatment::plot::{plot_curve, PlotCurve},
     messages::Algo,
-    write_in_file, Evaluation, KindEvaluation, Step,
+    network::{NetworkFaults, Partition},
+    write_in_file, ByzBehaviour, CrashPoint, DealerAttack, Evaluation, KindEvaluation, Step,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -27,6 +28,12 @@ pub struct SubArgs {
     /// The debit results of the state
     latency: HashMap<(Algo, Step), Curve>,
     latency_curve: Curve,
+    /// The number of nodes which produced an output in each operation, lower than n when nodes crash
+    outputs: HashMap<(Algo, Step), Vec<u16>>,
+    /// The most rounds taken by a binary agreement in each operation running some
+    rounds: HashMap<(Algo, Step), Vec<u16>>,
+    /// The number of operations which timed out before the nodes output
+    timeouts: HashMap<(Algo, Step), usize>,
     conclusion: Option<ResultCurves>,
     /// The output file for this subarg, is always a png
     output_file: String,
@@ -52,6 +59,55 @@ impl SubArgs {
         self.variation.set_steps(steps);
     }
 
+    pub fn set_secret(&mut self, secret: Vec<u8>) {
+        self.fields.set_secret(secret);
+    }
+
+    pub fn set_byz_behaviours(&mut self, behaviours: Vec<ByzBehaviour>) {
+        self.fields.set_byz_behaviours(behaviours);
+    }
+
+    pub fn set_faults(&mut self, faults: NetworkFaults) {
+        self.fields.set_faults(faults);
+    }
+
+    pub fn set_partitions(&mut self, partitions: Vec<Partition>) {
+        self.fields.set_partitions(partitions);
+    }
+
+    pub fn set_dealer_attacks(&mut self, attacks: Vec<(Algo, DealerAttack)>) {
+        self.fields.set_dealer_attacks(attacks);
+    }
+
+    pub fn set_crashes(&mut self, crashes: Vec<(u16, CrashPoint)>) {
+        self.fields.set_crashes(crashes);
+    }
+
+    pub fn set_weights(&mut self, weights: Vec<u16>) {
+        self.fields.set_weights(weights);
+    }
+
+    /// Checks the fields of every algo, step and size of the network evaluated by the state
+    pub fn check(&self) -> anyhow::Result<()> {
+        let mut fields = self.fields.clone();
+        let ns = self
+            .variation
+            .values(TypeField::N)
+            .cloned()
+            .unwrap_or_else(|| vec![self.fields.n()]);
+        for algo in self.variation.algos() {
+            for step in self.variation.steps().iter().filter(|s| algo.support(**s)) {
+                fields.set_algo(*algo);
+                fields.set_step(*step);
+                for n in &ns {
+                    fields.set(TypeField::N, *n);
+                    fields.check()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn variation_index(&self) -> usize {
         self.variation.get_variation_index()
     }
@@ -61,6 +117,11 @@ impl SubArgs {
         self.variation.steps().contains(&Step::Reconstruct)
     }
 
+    /// Returns the first step evaluated by the state
+    pub fn first_step(&self) -> Step {
+        self.variation.steps()[0]
+    }
+
     /// Return true if the state evaluates the sharing
     pub fn has_sharing(&self) -> bool {
         self.variation.steps().contains(&Step::Sharing)
@@ -77,9 +138,18 @@ impl SubArgs {
 
     /// Returns the max value of n in fields, but if the varied field is n then returns the maximum of the variation
     pub fn get_maximum_network_size(&self) -> u16 {
-        self.variation
+        let n = self
+            .variation
             .get_maximum_network_size()
-            .unwrap_or(self.fields.n())
+            .unwrap_or(self.fields.n());
+        if !self.variation.steps().contains(&Step::Reshare) {
+            return n;
+        }
+        let new_n = match self.fields.get(TypeField::NewN) {
+            0 => n,
+            new_n => new_n,
+        };
+        n.max(self.fields.new_offset() + new_n)
     }
 
     pub fn full_reset(&mut self) {
@@ -160,6 +230,90 @@ impl SubArgs {
         }
     }
 
+    pub fn record_outputs(&mut self, outputs: u16) {
+        self.outputs
+            .entry((self.algo(), self.get_step()))
+            .or_default()
+            .push(outputs)
+    }
+
+    pub fn record_rounds(&mut self, rounds: Option<u16>) {
+        if let Some(rounds) = rounds {
+            self.rounds
+                .entry((self.algo(), self.get_step()))
+                .or_default()
+                .push(rounds)
+        }
+    }
+
+    pub fn record_timeout(&mut self, timed_out: bool) {
+        if timed_out {
+            *self
+                .timeouts
+                .entry((self.algo(), self.get_step()))
+                .or_default() += 1
+        }
+    }
+
+    /// Minimum and maximum number of nodes with an output over the operations of each algo
+    fn outputs_summary(&self) -> String {
+        self.outputs
+            .iter()
+            .map(|((algo, step), outputs)| {
+                format!(
+                    "{} {}: {} to {} over {} operations",
+                    <&str>::from(*algo),
+                    String::from(*step),
+                    outputs.iter().min().unwrap(),
+                    outputs.iter().max().unwrap(),
+                    outputs.len()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Number of operations of each algo which timed out, over the operations run
+    fn timeouts_summary(&self) -> String {
+        if self.timeouts.is_empty() {
+            return String::from("none");
+        }
+        self.timeouts
+            .iter()
+            .map(|((algo, step), timeouts)| {
+                format!(
+                    "{} {}: {} over {} operations",
+                    <&str>::from(*algo),
+                    String::from(*step),
+                    timeouts,
+                    self.outputs.get(&(*algo, *step)).map_or(0, Vec::len)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Average and maximum rounds of the binary agreements over the operations of each algo
+    fn rounds_summary(&self) -> String {
+        if self.rounds.is_empty() {
+            return String::from("none");
+        }
+        self.rounds
+            .iter()
+            .map(|((algo, step), rounds)| {
+                format!(
+                    "{} {}: {:.2} on average, {} at most over {} operations",
+                    <&str>::from(*algo),
+                    String::from(*step),
+                    rounds.iter().map(|r| *r as f64).sum::<f64>() / rounds.len() as f64,
+                    rounds.iter().max().unwrap(),
+                    rounds.len()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     /// This function evolve the state by calling latency evolve on his variation. If it returns stop then the state will output. If it returns a conclusion, then it will store the result and continue its way.
     pub fn latency_evolve(
         &mut self,
@@ -475,7 +629,17 @@ Setup:
 n: {},
 t: {},
 l: {}
-nb_byz: {},
+nb_byz: {}, byzantine delay: {} ms,
+dealers: {},
+packing: {},
+total weight: {}, weighted t = {},
+new committee: {} nodes from {}, t = {},
+network faults: {},
+partitions: {},
+dealer attacks: {},
+nodes with an output: {},
+operations timed out after {} s: {},
+binary agreement rounds: {},
 hmt (latency): {},
 debit duration: {},
 variation on {} : {:?}
@@ -488,6 +652,39 @@ Results:
             self.fields.t(),
             self.fields.l(),
             self.fields.nb_byz(),
+            self.fields.byz_delay(),
+            self.fields.dealers(),
+            self.fields.packing(),
+            self.fields.total_weight(),
+            self.fields.weighted_t(),
+            self.fields.new_n(),
+            self.fields.new_offset(),
+            self.fields.new_t(),
+            self.fields
+                .faults()
+                .map_or(String::from("none"), |faults| faults.to_string()),
+            self.fields
+                .partitions()
+                .map_or(String::from("none"), |partitions| {
+                    partitions
+                        .iter()
+                        .map(|p| p.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }),
+            self.fields
+                .dealer_attacks()
+                .map_or(String::from("none"), |attacks| {
+                    attacks
+                        .iter()
+                        .map(|(algo, attack)| format!("{algo:?}: {attack:?}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }),
+            self.outputs_summary(),
+            self.fields.op_timeout().as_secs(),
+            self.timeouts_summary(),
+            self.rounds_summary(),
             self.latency_hmt,
             self.debit_duration,
             self.varied(),
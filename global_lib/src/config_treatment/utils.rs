@@ -3,6 +3,7 @@ pub type JsonMap = serde_json::Map<String, JsonValue>;
 use serde_json::Number;
 
 use super::result_fields::{Curve, ResultDuration};
+use crate::{messages::NameSpace, CrashPoint};
 
 pub fn adjust_curve(x_curve: &mut Curve, y_curve: &mut Curve) {
     assert!(x_curve.len() == y_curve.len());
@@ -80,6 +81,20 @@ pub fn serde_n_to_usize(n: &JsonValue) -> usize {
     }
 }
 
+/// Reads a crash given as {"node": i, "messages": k} or {"node": i, "phase": "broadcast"}
+pub fn extract_crash(v: &JsonValue) -> (u16, CrashPoint) {
+    let crash = extract_serde_obj(v);
+    let node = serde_n_to_u16(crash.get("node").expect("A crash needs a node"));
+    let point = match (crash.get("messages"), crash.get("phase")) {
+        (Some(k), None) => CrashPoint::Messages(serde_n_to_usize(k) as u32),
+        (None, Some(phase)) => {
+            CrashPoint::Phase(NameSpace::from(extract_serde_string(phase) as &str))
+        }
+        _ => panic!("A crash needs either a number of messages or a phase"),
+    };
+    (node, point)
+}
+
 pub fn vec_to_serde_arr<T: Into<u64> + Copy>(vec: &[T]) -> JsonValue {
     JsonValue::Array(
         vec.iter()
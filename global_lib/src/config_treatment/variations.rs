@@ -324,6 +324,18 @@ impl Variation {
         &self.data.algos
     }
 
+    /// Values taken by the field, None when it doesn't vary
+    pub fn values(&self, field: TypeField) -> Option<&Vec<u16>> {
+        if self.data.field == field {
+            return Some(&self.data.main_variation);
+        }
+        self.data
+            .subvariations
+            .iter()
+            .find(|(t, _)| *t == field)
+            .map(|(_, v)| v)
+    }
+
     pub fn algos_as_str(&self, step: Step) -> Vec<String> {
         self.data
             .algos
@@ -0,0 +1,82 @@
+use blstrs::{G1Projective, Scalar};
+use group::{ff::Field, Group};
+use rand::thread_rng;
+
+pub const DST_THRESHOLD_DECRYPT: &[u8] = b"SecretSharingTestbedThresholdDecrypt";
+
+pub type ElGamalCiphertext = (G1Projective, G1Projective); // (g^r, m * pk^r)
+/// The input of a decryption along with its encryptions to the public keys of every session
+pub type DecryptInput = (Vec<u8>, Vec<Vec<ElGamalCiphertext>>);
+
+/// Maps a payload to the secrets of a batch, 31 bytes per secret so that each chunk fits in a
+/// scalar. The secrets past the end of the payload are 0.
+pub fn payload_to_secrets(payload: &[u8], batch_size: usize) -> Vec<Scalar> {
+    let chunks = payload.chunks(31);
+    assert!(
+        chunks.len() <= batch_size,
+        "The payload doesn't fit in the batch"
+    );
+    let mut secrets = chunks
+        .map(|chunk| {
+            let mut bytes = [0; 32];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            Scalar::from_bytes_le(&bytes).unwrap()
+        })
+        .collect::<Vec<_>>();
+    secrets.resize(batch_size, Scalar::zero());
+    secrets
+}
+
+pub fn elgamal_plaintext(input: &[u8]) -> G1Projective {
+    G1Projective::hash_to_curve(input, DST_THRESHOLD_DECRYPT, b"plaintext")
+}
+
+/// Encrypts the plaintext of the input to each public key, with a fresh randomness every time.
+pub fn elgamal_encrypt(public_keys: &[G1Projective], input: &[u8]) -> Vec<ElGamalCiphertext> {
+    let m = elgamal_plaintext(input);
+    let mut rng = thread_rng();
+    public_keys
+        .iter()
+        .map(|pk| {
+            let r = Scalar::random(&mut rng);
+            (G1Projective::generator() * r, m + pk * r)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_to_secrets() {
+        let payload = (0..40).collect::<Vec<u8>>();
+        let secrets = payload_to_secrets(&payload, 3);
+        assert_eq!(secrets.len(), 3);
+        let mut first = [0; 32];
+        first[..31].copy_from_slice(&payload[..31]);
+        assert_eq!(secrets[0], Scalar::from_bytes_le(&first).unwrap());
+        let mut second = [0; 32];
+        second[..9].copy_from_slice(&payload[31..]);
+        assert_eq!(secrets[1], Scalar::from_bytes_le(&second).unwrap());
+        assert_eq!(secrets[2], Scalar::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "The payload doesn't fit in the batch")]
+    fn test_payload_too_large() {
+        payload_to_secrets(&[1; 63], 2);
+    }
+
+    #[test]
+    fn test_elgamal_encrypt() {
+        let sk = Scalar::random(thread_rng());
+        let pk = G1Projective::generator() * sk;
+        let first = elgamal_encrypt(&[pk], b"input");
+        let second = elgamal_encrypt(&[pk], b"input");
+        assert_ne!(first, second);
+        for (c1, c2) in first.into_iter().chain(second) {
+            assert_eq!(c2 - c1 * sk, elgamal_plaintext(b"input"));
+        }
+    }
+}
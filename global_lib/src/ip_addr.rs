@@ -40,7 +40,7 @@ impl IpV4 {
     }
 
     pub fn ip_from_str(s: &str) -> Result<Ip, String> {
-        let host = s.split('@').last().unwrap_or(s);
+        let host = s.split('@').next_back().unwrap_or(s);
 
         if let Ok(parsed) = host.parse::<IpAddr>() {
             return match parsed {
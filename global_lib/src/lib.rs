@@ -1,4 +1,5 @@
 pub mod config_treatment;
+pub mod crypto;
 pub mod ip_addr;
 pub mod macros;
 pub mod messages;
@@ -8,7 +9,7 @@ pub mod process_pool;
 pub mod settings;
 pub mod task_pool;
 
-use crate::messages::NameSpace;
+use crate::messages::{Algo, NameSpace};
 use ip_addr::IpV4;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -100,6 +101,18 @@ impl Evaluation {
         *step == Step::Reconstruct
     }
 
+    pub fn needs_shares(&self) -> bool {
+        matches!(
+            self.get_step(),
+            Step::Reconstruct
+                | Step::Sign
+                | Step::Decrypt
+                | Step::Refresh
+                | Step::Reshare
+                | Step::Triples
+        )
+    }
+
     pub fn change_step(&mut self, step: Step) {
         *self = match self {
             Evaluation::Debit(_) => Evaluation::Debit(step),
@@ -120,12 +133,22 @@ pub enum Step {
     Sharing,
     #[default]
     Reconstruct,
+    Beacon,
+    Sign,
+    Decrypt,
+    Refresh,
+    Reshare,
+    Triples,
 }
 
 impl Step {
     fn all() -> Vec<Self> {
         vec![Self::Sharing, Self::Reconstruct]
     }
+
+    pub fn is_sharing(&self) -> bool {
+        matches!(self, Step::Sharing | Step::Beacon | Step::Refresh)
+    }
 }
 
 impl From<Step> for String {
@@ -133,6 +156,12 @@ impl From<Step> for String {
         String::from(match step {
             Step::Sharing => "sharing",
             Step::Reconstruct => "reconstruct",
+            Step::Beacon => "beacon",
+            Step::Sign => "sign",
+            Step::Decrypt => "decrypt",
+            Step::Refresh => "refresh",
+            Step::Reshare => "reshare",
+            Step::Triples => "triples",
         })
     }
 }
@@ -141,11 +170,98 @@ impl From<&str> for Step {
         match s {
             "sharing" => Step::Sharing,
             "reconstruct" => Step::Reconstruct,
+            "beacon" => Step::Beacon,
+            "sign" => Step::Sign,
+            "decrypt" => Step::Decrypt,
+            "refresh" => Step::Refresh,
+            "reshare" => Step::Reshare,
+            "triples" => Step::Triples,
             _ => panic!("Unvalid step string"),
         }
     }
 }
 
+/// Misbehaviour of a byzantine node
+#[derive(PartialEq, Clone, Serialize, Deserialize, Copy, Debug, Default)]
+pub enum ByzBehaviour {
+    /// Sends no message at all
+    Silent,
+    /// Sends its messages to half of the recipients and garbage to the other half
+    Equivocating,
+    /// Replaces the content of its messages by random bytes
+    Garbage,
+    /// Holds its messages back before sending them
+    Delaying,
+    /// Corrupts its shares and signatures and skips the reconstruction
+    #[default]
+    WrongShares,
+}
+
+impl From<&str> for ByzBehaviour {
+    fn from(s: &str) -> ByzBehaviour {
+        match s {
+            "silent" => ByzBehaviour::Silent,
+            "equivocating" => ByzBehaviour::Equivocating,
+            "garbage" => ByzBehaviour::Garbage,
+            "delaying" => ByzBehaviour::Delaying,
+            "wrong_shares" => ByzBehaviour::WrongShares,
+            _ => panic!("Unvalid byzantine behaviour: {s}"),
+        }
+    }
+}
+
+/// Point of an operation where a node crashes
+#[derive(PartialEq, Clone, Serialize, Deserialize, Copy, Debug)]
+pub enum CrashPoint {
+    /// Once it sent the given number of messages
+    Messages(u32),
+    /// When it is about to send its first message of the given subprotocol
+    Phase(NameSpace),
+}
+
+/// Misbehaviour of a corrupted dealer during a sharing
+#[derive(PartialEq, Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DealerAttack {
+    /// Sends nothing to the given nodes
+    Omit(Vec<u16>),
+    /// Sends to the given nodes shares that don't match the commitment
+    WrongShares(Vec<u16>),
+    /// Broadcasts a commitment failing the low degree test
+    BadCommitment,
+    /// Proposes the commitment of another polynomial to the given nodes, with their shares of it
+    Equivocate(Vec<u16>),
+}
+
+impl DealerAttack {
+    /// Whether the dealer of the algo knows how to run the attack
+    pub fn supported_by(&self, algo: Algo) -> bool {
+        match self {
+            DealerAttack::Omit(_) => matches!(
+                algo,
+                Algo::AvssSimpl
+                    | Algo::DualAvssSimpl
+                    | Algo::Bingo
+                    | Algo::LightWeight
+                    | Algo::HbAvss
+                    | Algo::Haven
+                    | Algo::Feldman
+            ),
+            DealerAttack::WrongShares(_) => matches!(
+                algo,
+                Algo::AvssSimpl
+                    | Algo::DualAvssSimpl
+                    | Algo::LightWeight
+                    | Algo::HbAvss
+                    | Algo::Feldman
+            ),
+            DealerAttack::BadCommitment | DealerAttack::Equivocate(_) => {
+                matches!(algo, Algo::LightWeight | Algo::HbAvss)
+            }
+        }
+    }
+}
+
 pub fn async_private_message(ip: IpV4, message: Vec<u8>, id: u64, my_id: u16) {
     tokio::spawn(
         async move { private_message(&mut connect(&ip).await, &message, id, my_id).await },
@@ -197,7 +313,7 @@ pub async fn private_message_multiple(
     });
     stream.write_all(&metinfo).await.unwrap();
     for message in messages {
-        stream.write_all(&message).await.unwrap();
+        stream.write_all(message).await.unwrap();
     }
     stream.flush().await.expect("Failed to flush");
 }
@@ -209,7 +325,7 @@ pub async fn private_message(stream: &mut TcpStream, message: &[u8], op_id: u64,
         sender_id: my_id,
     });
     stream.write_all(&metinfo).await.unwrap();
-    stream.write_all(&message).await.unwrap();
+    stream.write_all(message).await.unwrap();
     stream.flush().await.expect("Failed to flush");
 }
 
@@ -5,6 +5,29 @@ macro_rules! dec {
     }};
 }
 
+/// Decodes the bytes, running the fallback (return by default) when they are malformed
+#[macro_export]
+macro_rules! try_dec {
+    (@fallback) => {
+        return
+    };
+    (@fallback $fallback:expr) => {
+        $fallback
+    };
+    ($bytes:ident[$range:expr] $(, $fallback:expr)?) => {
+        match $bytes.get($range).map(|bytes| bincode::deserialize(bytes)) {
+            Some(Ok(val)) => val,
+            _ => $crate::try_dec!(@fallback $($fallback)?),
+        }
+    };
+    ($bytes:expr $(, $fallback:expr)?) => {
+        match bincode::deserialize(&($bytes[..])) {
+            Ok(val) => val,
+            Err(_) => $crate::try_dec!(@fallback $($fallback)?),
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! count_tts {
     () => { 0 };
@@ -64,11 +87,20 @@ macro_rules! as_number {
             $($variant),*
         }
 
+        impl $enum_name {
+            pub fn from_checked(value: $t) -> Option<Self> {
+                match value {
+                    $(x if x == $enum_name::$variant as $t => Some($enum_name::$variant)),*,
+                    _ => None,
+                }
+            }
+        }
+
         impl From<$t> for $enum_name {
             fn from(value: $t) -> Self {
-                match value {
-                    $(x if x == $enum_name::$variant as $t => $enum_name::$variant),*,
-                    _ => panic!("Invalid value for enum: {value}"),
+                match $enum_name::from_checked(value) {
+                    Some(variant) => variant,
+                    None => panic!("Invalid value for enum: {value}"),
                 }
             }
         }
@@ -85,37 +117,44 @@ macro_rules! as_number {
 macro_rules! select {
     (self_select, $enum_name:ident, $bytes_message:ident, $first:expr, $($variant:ident => $function:ident $($bonus_param:expr)?),* $(,)?) => {
         tokio::spawn(async move {
-            match $enum_name::from($bytes_message[0]) {
+            match $bytes_message.first().copied().and_then($enum_name::from_checked) {
                 $(
-                    $enum_name::$variant => $first.$function(&$bytes_message[1..], $($bonus_param)?).await,
+                    Some($enum_name::$variant) => $first.$function(&$bytes_message[1..], $($bonus_param)?).await,
                 )*
+                None => {}
             }
         });
     };
      (on_myself, $enum_name:ident, $bytes_message:ident, $node:ident, $($variant:ident => $function:ident $($bonus_param:expr)?),* $(,)?) => {
         tokio::spawn(async move {
-            match $enum_name::from($bytes_message[0]) {
+            match $bytes_message.first().copied().and_then($enum_name::from_checked) {
                 $(
-                    $enum_name::$variant => Self::$function($node, &$bytes_message[1..], $($bonus_param)?).await,
+                    Some($enum_name::$variant) => Self::$function($node, &$bytes_message[1..], $($bonus_param)?).await,
                 )*
+                None => {}
             }
         });
     };
     ($(wrapped_select,)?as_vec $enum_name:ident, $bytes_message:ident, $node:ident, $($variant:ident => $function:ident $($bonus_param:expr)?),* $(,)?) => {
         tokio::spawn(async move {
-            match $enum_name::from($bytes_message.remove(0)) {
+            match $bytes_message.first().copied().and_then($enum_name::from_checked) {
                 $(
-                    $enum_name::$variant => $function($node, $bytes_message, $($bonus_param)?).await,
+                    Some($enum_name::$variant) => {
+                        $bytes_message.remove(0);
+                        $function($node, $bytes_message, $($bonus_param)?).await
+                    }
                 )*
+                None => {}
             }
         });
     };
     ($(wrapped_select,)? $enum_name:ident, $bytes_message:ident, $node:ident, $($variant:ident => $function:ident $($bonus_param:expr)?),* $(,)?) => {
         tokio::spawn(async move {
-            match $enum_name::from($bytes_message[0]) {
+            match $bytes_message.first().copied().and_then($enum_name::from_checked) {
                 $(
-                    $enum_name::$variant => $function($node, &$bytes_message[1..], $($bonus_param)?).await,
+                    Some($enum_name::$variant) => $function($node, &$bytes_message[1..], $($bonus_param)?).await,
                 )*
+                None => {}
             }
         });
     }
@@ -227,3 +266,19 @@ macro_rules! with_getters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_try_dec() {
+        fn decode(bytes: &[u8]) -> Option<(u16, bool)> {
+            Some(try_dec!(bytes[1..], return None))
+        }
+        let mut bytes = vec![0];
+        bytes.append(&mut bincode::serialize(&(3u16, true)).unwrap());
+        assert_eq!(decode(&bytes), Some((3, true)));
+        assert_eq!(decode(&bytes[..2]), None);
+        assert_eq!(decode(&[]), None);
+        assert!(crate::messages::NameSpace::from_checked(u8::MAX).is_none());
+    }
+}
@@ -23,6 +23,9 @@ as_number!(
         Send,
         Echo,
         Ready,
+        ReconstructShare,
+        CommRequest,
+        Comm,
     },
     derive(Debug)
 );
@@ -45,6 +48,16 @@ as_number!(
     }
 );
 
+as_number!(
+    u8,
+    enum AbaCommand {
+        BVal,
+        Aux,
+        Coin,
+        Term,
+    }
+);
+
 as_number!(
     u8,
     enum BadgerCommand {
@@ -52,6 +65,13 @@ as_number!(
     }
 );
 
+as_number!(
+    u8,
+    enum LightWeightCommand {
+        ReconstructShare,
+    }
+);
+
 as_number!(
     u8,
     enum BroadcastCommand {
@@ -88,6 +108,39 @@ as_number!(
     }
 );
 
+as_number!(
+    u8,
+    enum SignCommand {
+        PartialSign,
+    }
+);
+
+as_number!(
+    u8,
+    enum DecryptCommand {
+        DecryptionShare,
+    }
+);
+
+as_number!(
+    u8,
+    enum ReshareCommand {
+        Ack,
+        Complaint,
+        Release,
+        Agreed,
+    }
+);
+
+as_number!(
+    u8,
+    enum TriplesCommand {
+        Ack,
+        Complaint,
+        Release,
+    }
+);
+
 as_number!(
     u8,
     enum BingoCommand {
@@ -104,6 +157,25 @@ as_number!(
     enum HbAvssCommand {
         Complaint,
         Assist,
+        ReconstructShare,
+    }
+);
+
+as_number!(
+    u8,
+    enum FeldmanCommand {
+        Share,
+        Echo,
+        Complaint,
+        Reveal,
+        ReconstructShare,
+    }
+);
+
+as_number!(
+    u8,
+    enum NiPvssCommand {
+        ReconstructShare,
     }
 );
 
@@ -121,10 +193,44 @@ as_number!(
         Haven,
         OneSidedVote,
         DisperseRetrieve,
+        Feldman,
+        NiPvss,
+        Aba,
+        Acs,
+        Sign,
+        Decrypt,
+        Reshare,
+        Triples,
     },
-    derive(Clone, Copy, Debug)
+    derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)
 );
 
+impl From<&str> for NameSpace {
+    fn from(s: &str) -> Self {
+        match s {
+            "broadcast" => NameSpace::Broadcast,
+            "secure_msg_dis" => NameSpace::SecureMsgDis,
+            "avss_simpl" => NameSpace::AvssSimpl,
+            "bingo" => NameSpace::Bingo,
+            "lightweight" => NameSpace::LightWeight,
+            "badger" => NameSpace::Badger,
+            "hbacss" => NameSpace::HbAvss,
+            "haven" => NameSpace::Haven,
+            "one_sided_vote" => NameSpace::OneSidedVote,
+            "disperse_retrieve" => NameSpace::DisperseRetrieve,
+            "feldman" => NameSpace::Feldman,
+            "ni_pvss" => NameSpace::NiPvss,
+            "aba" => NameSpace::Aba,
+            "acs" => NameSpace::Acs,
+            "sign" => NameSpace::Sign,
+            "decrypt" => NameSpace::Decrypt,
+            "reshare" => NameSpace::Reshare,
+            "triples" => NameSpace::Triples,
+            _ => panic!("Unvalid subprotocol: {s}"),
+        }
+    }
+}
+
 as_number!(
     u8,
     enum ManagerCode {
@@ -140,6 +246,9 @@ as_number!(
         Output,
         NodeReady,
         PoolCleaned,
+        PrivateShare,
+        Crashed,
+        PublicKeys,
     },
     derive(Debug)
 );
@@ -154,6 +263,11 @@ pub enum Algo {
     Badger,
     HbAvss,
     Haven,
+    Feldman,
+    NiPvss,
+    Adkg,
+    Aba,
+    Acs,
 }
 
 impl From<Algo> for NameSpace {
@@ -165,6 +279,10 @@ impl From<Algo> for NameSpace {
             Algo::Badger => NameSpace::Badger,
             Algo::HbAvss => NameSpace::HbAvss,
             Algo::Haven => NameSpace::Haven,
+            Algo::Feldman | Algo::Adkg => NameSpace::Feldman,
+            Algo::NiPvss => NameSpace::NiPvss,
+            Algo::Aba => NameSpace::Aba,
+            Algo::Acs => NameSpace::Acs,
         }
     }
 }
@@ -190,6 +308,11 @@ impl Algo {
             Algo::DualAvssSimpl => "midnight-blue",
             Algo::HbAvss => "purple",
             Algo::Badger => "black",
+            Algo::Feldman => "orange",
+            Algo::NiPvss => "brown",
+            Algo::Adkg => "magenta",
+            Algo::Aba => "gray",
+            Algo::Acs => "olive",
         }
     }
 
@@ -199,19 +322,46 @@ impl Algo {
 
     pub fn step_supported(&self) -> Vec<Step> {
         match self {
-            Algo::Badger | Algo::Bingo | Algo::AvssSimpl | Algo::DualAvssSimpl => {
-                vec![Step::Sharing, Step::Reconstruct]
-            }
-            Algo::LightWeight | Algo::HbAvss | Algo::Haven => vec![Step::Sharing],
+            Algo::Badger => vec![Step::Sharing, Step::Reconstruct],
+            Algo::Adkg => vec![Step::Sharing, Step::Sign, Step::Decrypt],
+            Algo::Aba | Algo::Acs => vec![Step::Sharing],
+            Algo::Bingo
+            | Algo::AvssSimpl
+            | Algo::DualAvssSimpl
+            | Algo::LightWeight
+            | Algo::HbAvss
+            | Algo::Haven
+            | Algo::NiPvss => vec![Step::Sharing, Step::Reconstruct, Step::Beacon],
+            Algo::Feldman => vec![
+                Step::Sharing,
+                Step::Reconstruct,
+                Step::Beacon,
+                Step::Sign,
+                Step::Decrypt,
+                Step::Refresh,
+                Step::Reshare,
+                Step::Triples,
+            ],
         }
     }
 
+    /// The algos running binary agreements, their common coins are threshold signatures under a
+    /// key dealt beforehand by a Feldman sharing
+    pub fn uses_aba(&self) -> bool {
+        matches!(self, Algo::Aba | Algo::Acs)
+    }
+
     pub fn get_subprotocols(self) -> Vec<NameSpace> {
         match self {
-            Algo::Haven => vec![],
-            Algo::Bingo | Algo::AvssSimpl | Algo::Badger | Algo::DualAvssSimpl => {
-                vec![NameSpace::Broadcast]
-            }
+            Algo::Haven | Algo::Aba => vec![],
+            Algo::Bingo
+            | Algo::AvssSimpl
+            | Algo::Badger
+            | Algo::DualAvssSimpl
+            | Algo::Feldman
+            | Algo::NiPvss
+            | Algo::Adkg => vec![NameSpace::Broadcast],
+            Algo::Acs => vec![NameSpace::Broadcast, NameSpace::Aba],
             Algo::HbAvss => vec![
                 NameSpace::Broadcast,
                 NameSpace::OneSidedVote,
@@ -236,6 +386,11 @@ impl From<Algo> for &'static str {
             Algo::Badger => "Honey Badger",
             Algo::HbAvss => "hbACSS",
             Algo::Haven => "Haven",
+            Algo::Feldman => "Feldman",
+            Algo::NiPvss => "NiPVSS",
+            Algo::Adkg => "ADKG",
+            Algo::Aba => "ABA",
+            Algo::Acs => "ACS",
         }
     }
 }
@@ -256,6 +411,11 @@ impl From<&str> for Algo {
             "lightweight" => Algo::LightWeight,
             "badger" => Algo::Badger,
             "hbacss" => Algo::HbAvss,
+            "feldman" => Algo::Feldman,
+            "ni_pvss" => Algo::NiPvss,
+            "adkg" => Algo::Adkg,
+            "aba" => Algo::Aba,
+            "acs" => Algo::Acs,
             _ => panic!("Algo doesn't exists"),
         }
     }
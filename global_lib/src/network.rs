@@ -1,14 +1,130 @@
-use crate::{connect, ip_addr::IpV4, private_message, wrap, OpId, Wrapped};
+use crate::{
+    connect, ip_addr::IpV4, private_message, settings::REORDER_DELAY, wrap, OpId, Wrapped,
+};
 use crate::{KindEvaluation, NodeId};
-use rand::{seq::SliceRandom, thread_rng};
-use std::sync::Arc;
-use tokio::{io::AsyncWriteExt, net::TcpStream};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::{io::AsyncWriteExt, net::TcpStream, time::sleep};
 
 #[derive(Default)]
 pub struct Network {
     network: Vec<Wrapped<Option<TcpStream>>>,
     ips: Vec<IpV4>,
     mode: KindEvaluation,
+    delay: Option<Duration>,
+    faults: Option<NetworkFaults>,
+    partitions: Vec<Partition>,
+    start: Option<Instant>, // Beginning of the operation, the partitions are timed from it
+}
+
+/// Faults of a link, each message being dropped, delayed, duplicated or held back behind the
+/// following ones at random
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkFaults {
+    pub drop: f64,      // Probability to drop a message
+    pub delay: u64,     // Maximum extra delay of a message, in ms
+    pub duplicate: f64, // Probability to send a message twice
+    pub reorder: f64,   // Probability to hold a message back so that the next ones overtake it
+}
+
+/// Faults of the link from a node to another, in place of the ones of every link
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LinkOverride {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub faults: LinkFaults,
+}
+
+/// Faults injected on the links between the nodes, `all` on every link but the ones of `links`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkFaults {
+    pub all: LinkFaults,
+    pub links: Vec<LinkOverride>,
+}
+
+impl LinkFaults {
+    pub fn check(&self) {
+        for p in [self.drop, self.duplicate, self.reorder] {
+            assert!(
+                (0.0..=1.0).contains(&p),
+                "A fault probability must be in [0, 1]"
+            );
+        }
+    }
+}
+
+impl NetworkFaults {
+    pub fn check(&self) {
+        self.all.check();
+        for link in &self.links {
+            link.faults.check();
+        }
+    }
+
+    /// Faults of the link from a node to another, the last override of the link if any
+    pub fn on(&self, from: NodeId, to: NodeId) -> &LinkFaults {
+        self.links
+            .iter()
+            .rev()
+            .find(|link| (link.from, link.to) == (from, to))
+            .map_or(&self.all, |link| &link.faults)
+    }
+}
+
+/// Groups of nodes that can't talk to each other between `from` and `to` ms of an operation, the
+/// nodes of no group forming one more group
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Partition {
+    pub groups: Vec<Vec<NodeId>>,
+    pub from: u64,
+    pub to: u64,
+}
+
+impl Partition {
+    fn group(&self, i: NodeId) -> Option<usize> {
+        self.groups.iter().position(|group| group.contains(&i))
+    }
+
+    /// Whether the link between a and b is cut at the given time of the operation
+    pub fn cuts(&self, a: NodeId, b: NodeId, at: Duration) -> bool {
+        (self.from..self.to).contains(&(at.as_millis() as u64)) && self.group(a) != self.group(b)
+    }
+}
+
+impl Display for Partition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} from {} to {} ms", self.groups, self.from, self.to)
+    }
+}
+
+impl Display for LinkFaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "drop {}%, delay up to {} ms, duplicate {}%, reorder {}%",
+            self.drop * 100.0,
+            self.delay,
+            self.duplicate * 100.0,
+            self.reorder * 100.0
+        )
+    }
+}
+
+impl Display for NetworkFaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.all)?;
+        for link in &self.links {
+            write!(f, "; {} to {}: {}", link.from, link.to, link.faults)?;
+        }
+        Ok(())
+    }
 }
 
 impl Network {
@@ -17,6 +133,10 @@ impl Network {
             mode: KindEvaluation::Latency,
             network: Vec::new(),
             ips: Vec::new(),
+            delay: None,
+            faults: None,
+            partitions: Vec::new(),
+            start: None,
         }
     }
 
@@ -37,43 +157,106 @@ impl Network {
         self.mode = KindEvaluation::Latency
     }
 
+    /// Holds every message sent through this network back for the given duration
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = Some(delay)
+    }
+
+    /// Times the partitions from the start of the operation, as given by the interface
+    pub fn set_partitions(&mut self, partitions: Vec<Partition>, start: SystemTime) {
+        let elapsed = SystemTime::now().duration_since(start).unwrap_or_default();
+        let now = Instant::now();
+        self.partitions = partitions;
+        self.start = Some(now.checked_sub(elapsed).unwrap_or(now));
+    }
+
+    pub fn set_faults(&mut self, faults: NetworkFaults) {
+        self.faults = Some(faults)
+    }
+
     pub fn ips(&self) -> &Vec<IpV4> {
         &self.ips
     }
 
     pub fn give_message(&self, index: usize, msg: Vec<u8>, id: OpId, my_id: NodeId) {
-        let ip = self.ips[index];
-        match self.mode {
-            KindEvaluation::Debit => {
-                let stream = self.network[index].clone();
-                tokio::spawn(async move {
-                    let mut stream = stream.lock().await;
-                    if stream.is_some() {
-                        private_message(stream.as_mut().unwrap(), &msg, id, my_id).await
-                    } else {
-                        let mut s = connect(&ip).await;
-                        private_message(&mut s, &msg, id, my_id).await;
-                        let _ = stream.insert(s);
-                    }
-                });
-            }
-            KindEvaluation::Latency => {
-                tokio::spawn(async move {
-                    let mut stream = connect(&ip).await;
-                    private_message(&mut stream, &msg, id, my_id).await;
-                    stream.shutdown().await.unwrap();
-                });
-            }
-        }
+        self.message(index, Arc::new(msg), id, my_id)
     }
 
     pub fn message(&self, index: usize, msg: Arc<Vec<u8>>, id: OpId, my_id: NodeId) {
+        for delay in self.copies(my_id, index) {
+            let delay = self.through_partitions(my_id, index, delay);
+            self.send_after(index, msg.clone(), id, my_id, delay)
+        }
+    }
+
+    /// Delays of the copies of a message that are actually sent, as decided by the faults of its link
+    fn copies(&self, from: NodeId, to: usize) -> Vec<Option<Duration>> {
+        let faults = match &self.faults {
+            Some(faults) => faults.on(from, to as NodeId),
+            None => return vec![self.delay],
+        };
+        let mut rng = thread_rng();
+        if rng.gen_bool(faults.drop) {
+            return Vec::new();
+        }
+        let nb_copies = if rng.gen_bool(faults.duplicate) { 2 } else { 1 };
+        (0..nb_copies)
+            .map(|_| {
+                let mut delay = self.delay.unwrap_or_default()
+                    + Duration::from_millis(rng.gen_range(0..=faults.delay));
+                if rng.gen_bool(faults.reorder) {
+                    delay += REORDER_DELAY;
+                }
+                Some(delay)
+            })
+            .collect()
+    }
+
+    /// Holds a message back until the partitions cutting its link when it leaves have healed
+    fn through_partitions(
+        &self,
+        from: NodeId,
+        to: usize,
+        delay: Option<Duration>,
+    ) -> Option<Duration> {
+        let start = match self.start {
+            Some(start) if !self.partitions.is_empty() => start,
+            _ => return delay,
+        };
+        let now = start.elapsed();
+        let mut leaves = now + delay.unwrap_or_default();
+        while let Some(partition) = self
+            .partitions
+            .iter()
+            .find(|p| p.cuts(from, to as NodeId, leaves))
+        {
+            leaves = Duration::from_millis(partition.to);
+        }
+        Some(leaves - now)
+    }
+
+    /// Whether the messages go out as they are sent, broadcasts can then share a single task
+    fn is_reliable(&self) -> bool {
+        self.faults.is_none() && self.partitions.is_empty()
+    }
+
+    fn send_after(
+        &self,
+        index: usize,
+        msg: Arc<Vec<u8>>,
+        id: OpId,
+        my_id: NodeId,
+        delay: Option<Duration>,
+    ) {
         let ip = self.ips[index];
         match self.mode {
             KindEvaluation::Debit => {
                 let stream = self.network[index].clone();
                 // println!("Sending msg to {index} as {my_id}");
                 tokio::spawn(async move {
+                    if let Some(delay) = delay {
+                        sleep(delay).await
+                    }
                     let mut stream = stream.lock().await;
                     if stream.is_some() {
                         private_message(stream.as_mut().unwrap(), &msg, id, my_id).await
@@ -87,6 +270,9 @@ impl Network {
             }
             KindEvaluation::Latency => {
                 tokio::spawn(async move {
+                    if let Some(delay) = delay {
+                        sleep(delay).await
+                    }
                     let mut stream = connect(&ip).await;
                     private_message(&mut stream, &msg, id, my_id).await;
                     stream.shutdown().await.unwrap();
@@ -133,21 +319,31 @@ impl Network {
     ) {
         let msg = Arc::new(msg);
         match self.mode {
-            KindEvaluation::Debit => {
-                for i in to_contact.unwrap_or((0..self.len()).collect()) {
-                    self.message(i, msg.clone(), id, my_id)
-                }
-            }
-            KindEvaluation::Latency => {
+            KindEvaluation::Latency if self.is_reliable() => {
                 let ips = self.ips.clone();
                 let len = self.full_len();
+                let delay = self.delay;
                 tokio::spawn(async move {
+                    if let Some(delay) = delay {
+                        sleep(delay).await
+                    }
                     for i in to_contact.unwrap_or((0..len).collect()) {
                         let mut stream = connect(&ips[i]).await;
                         private_message(&mut stream, &msg, id, my_id).await
                     }
                 });
             }
+            // Each link draws its own faults
+            KindEvaluation::Latency => {
+                for i in to_contact.unwrap_or((0..self.full_len()).collect()) {
+                    self.message(i, msg.clone(), id, my_id)
+                }
+            }
+            KindEvaluation::Debit => {
+                for i in to_contact.unwrap_or((0..self.len()).collect()) {
+                    self.message(i, msg.clone(), id, my_id)
+                }
+            }
         }
     }
 
@@ -160,6 +356,75 @@ impl Network {
                 Vec::new()
             },
             ips: self.ips[0..n].to_vec(),
+            delay: None,
+            faults: None,
+            partitions: Vec::new(),
+            start: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faults_on_link() {
+        let drop = |drop| LinkFaults {
+            drop,
+            ..Default::default()
+        };
+        let faults = NetworkFaults {
+            all: drop(0.1),
+            links: vec![
+                LinkOverride {
+                    from: 0,
+                    to: 1,
+                    faults: drop(0.5),
+                },
+                LinkOverride {
+                    from: 0,
+                    to: 1,
+                    faults: drop(1.0),
+                },
+            ],
+        };
+        faults.check();
+        assert_eq!(*faults.on(0, 1), drop(1.0));
+        assert_eq!(*faults.on(1, 0), drop(0.1));
+        assert_eq!(*faults.on(2, 3), drop(0.1));
+    }
+
+    #[test]
+    #[should_panic(expected = "A fault probability must be in [0, 1]")]
+    fn test_faults_invalid_probability() {
+        let faults = NetworkFaults {
+            links: vec![LinkOverride {
+                from: 0,
+                to: 1,
+                faults: LinkFaults {
+                    reorder: 1.5,
+                    ..Default::default()
+                },
+            }],
+            ..Default::default()
+        };
+        faults.check();
+    }
+
+    #[test]
+    fn test_partition_cuts() {
+        let partition = Partition {
+            groups: vec![vec![0, 1], vec![2]],
+            from: 100,
+            to: 200,
+        };
+        let at = Duration::from_millis;
+        assert!(partition.cuts(0, 2, at(100)));
+        assert!(partition.cuts(3, 1, at(150)));
+        assert!(!partition.cuts(0, 1, at(150)));
+        assert!(!partition.cuts(3, 4, at(150)));
+        assert!(!partition.cuts(0, 2, at(99)));
+        assert!(!partition.cuts(0, 2, at(200)));
+    }
+}
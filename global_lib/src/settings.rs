@@ -12,6 +12,8 @@ pub static SPAMER_LATENCY_LIMIT: u128 = 1;
 pub static LOCAL_IP: &str = "127.0.0.1";
 pub static SPAMER_REDUCTION: f32 = 0.8;
 pub static TIMEOUT: Duration = Duration::from_secs(25);
+pub static REORDER_DELAY: Duration = Duration::from_millis(50);
+pub static OP_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub const BASE_CAPACITY: usize = 2000;
 pub static INTERFACE_PORT: u16 = 18_800;
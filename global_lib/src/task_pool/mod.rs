@@ -1,5 +1,6 @@
 pub mod errors;
 pub mod task;
+#[allow(clippy::module_inception)]
 pub mod task_pool;
 
 pub type Wrapped<T> = Arc<RwLock<T>>;
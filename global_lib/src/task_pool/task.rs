@@ -13,6 +13,11 @@ pub struct TaskInterface<Output> {
 }
 
 impl<Output> TaskInterface<Output> {
+    /// Creates an interface outputing on the given sender, useful to split a task in subtasks
+    pub fn new(id: OpId, output_sender: TaskOutputSender<Output>) -> Self {
+        Self { id, output_sender }
+    }
+
     /// Returns the id of the task
     pub fn id(&self) -> OpId {
         self.id
@@ -31,6 +31,7 @@ pub struct PoolTaskEnded<Output: Send> {
 
 pub type OutputSender<Output> = Sender<PoolTaskEnded<Output>>;
 pub type OutputReceiver<Output> = Receiver<PoolTaskEnded<Output>>;
+type CleaningNotifier = (Sender<Result<(), PoolError>>, Option<usize>);
 
 struct WrappedTaskPool<Message: Send, Output: Send> {
     pool: HashMap<OpId, Sender<Message>>,
@@ -38,7 +39,7 @@ struct WrappedTaskPool<Message: Send, Output: Send> {
     output_result_senders: Vec<OutputSender<Output>>,
     task_creation_senders: HashMap<OpId, Vec<Sender<()>>>,
     task_states: HashMap<OpId, TaskState>,
-    cleaning_notifier: Option<(Sender<Result<(), PoolError>>, Option<usize>)>,
+    cleaning_notifier: Option<CleaningNotifier>,
 }
 
 impl<Message: Send + 'static, Output: Send + 'static + Sync> WrappedTaskPool<Message, Output> {
@@ -0,0 +1,140 @@
+use blstrs::{G1Projective, Scalar};
+use global_lib::config_treatment::fields::Fields;
+use group::{
+    ff::{Field, PrimeField},
+    Group,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+/// Index of a node, its shares by evaluation point and the feldman commitment it checked them
+/// against
+pub type PrivateShare = (u16, Vec<(u16, Vec<Scalar>)>, Vec<Vec<G1Projective>>);
+
+/// Receives the shares of a private reconstruction and interpolates the secrets on its own
+pub struct Client {
+    fields: Fields,
+    pending: Vec<Vec<(u16, Vec<Scalar>)>>,
+    comms: Vec<(Vec<Vec<G1Projective>>, u16)>,
+    received: HashSet<u16>, // Nodes whose shares were received
+    comm: Option<Vec<Vec<G1Projective>>>,
+    shares: HashMap<u16, Vec<Scalar>>,
+    roots: Vec<Scalar>,
+}
+
+impl Client {
+    pub fn new(fields: Fields) -> Self {
+        let nb_points = fields.total_weight() as usize;
+        let log_n = nb_points.next_power_of_two().trailing_zeros();
+        let mut omega = Scalar::root_of_unity();
+        for _ in log_n..Scalar::S {
+            omega = omega.square();
+        }
+        let mut roots = Vec::with_capacity(nb_points);
+        let mut root = Scalar::one();
+        for _ in 0..nb_points {
+            roots.push(root);
+            root *= omega;
+        }
+        Client {
+            fields,
+            pending: Vec::new(),
+            comms: Vec::new(),
+            received: HashSet::new(),
+            comm: None,
+            shares: HashMap::new(),
+            roots,
+        }
+    }
+
+    pub fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
+    /// Returns the secrets once enough valid shares have been received. The commitment is trusted
+    /// once t + 1 nodes sent it, so at least one of them is honest. A node is heard once, and only
+    /// for its own evaluation points.
+    pub fn new_share(&mut self, (node, shares, comm): PrivateShare) -> Option<Vec<Scalar>> {
+        if node >= self.fields.n() || !self.received.insert(node) {
+            return None;
+        }
+        let points = self.points(node);
+        if shares.iter().any(|(i, _)| !points.contains(i)) {
+            return None;
+        }
+        if self.comm.is_none() && self.fits(&comm) {
+            match self.comms.iter_mut().find(|(c, _)| *c == comm) {
+                Some((_, count)) => *count += 1,
+                None => self.comms.push((comm, 1)),
+            }
+            let trusted = self
+                .comms
+                .iter()
+                .find(|(_, count)| *count > self.fields.t())
+                .map(|(c, _)| c.clone());
+            self.comm = trusted;
+        }
+        self.pending.push(shares);
+        let comm = self.comm.clone()?;
+        for shares in std::mem::take(&mut self.pending) {
+            if shares.iter().all(|(i, s)| self.verify(&comm, *i, s)) {
+                self.shares.extend(shares);
+            }
+        }
+        (self.shares.len() > self.fields.weighted_t() as usize).then(|| self.interpolate())
+    }
+
+    /// Evaluation points held by the node, one after the other in the order of the nodes
+    fn points(&self, node: u16) -> Range<u16> {
+        let start = (0..node).map(|i| self.fields.weight(i)).sum();
+        start..start + self.fields.weight(node)
+    }
+
+    /// Whether the commitment has a line of the degree of the sharing per secret of the batch
+    fn fits(&self, comm: &[Vec<G1Projective>]) -> bool {
+        let degree = self.fields.weighted_t() as usize;
+        comm.len() == self.fields.batch_size() as usize
+            && comm.iter().all(|line| line.len() == degree + 1)
+    }
+
+    fn verify(&self, comm: &[Vec<G1Projective>], i: u16, evals: &[Scalar]) -> bool {
+        let x = self.roots[i as usize];
+        evals.len() == comm.len()
+            && comm.iter().zip(evals).all(|(line, s)| {
+                let mut x_pow = Scalar::one();
+                let mut expected = G1Projective::identity();
+                for c in line {
+                    expected += c * x_pow;
+                    x_pow *= x;
+                }
+                expected == G1Projective::generator() * s
+            })
+    }
+
+    fn interpolate(&self) -> Vec<Scalar> {
+        let points = self.shares.keys().copied().collect::<Vec<_>>();
+        let lagr = points
+            .iter()
+            .map(|i| {
+                let xi = self.roots[*i as usize];
+                points
+                    .iter()
+                    .filter(|j| *j != i)
+                    .fold(Scalar::one(), |acc, j| {
+                        let xj = self.roots[*j as usize];
+                        acc * xj * (xj - xi).invert().unwrap()
+                    })
+            })
+            .collect::<Vec<_>>();
+        (0..self.fields.batch_size() as usize)
+            .map(|b| {
+                points
+                    .iter()
+                    .zip(lagr.iter())
+                    .fold(Scalar::zero(), |acc, (i, l)| acc + self.shares[i][b] * l)
+            })
+            .collect()
+    }
+}
@@ -1,13 +1,17 @@
 use std::{
+    collections::HashMap,
     env,
     io::Read,
     process::exit,
     time::{Duration, Instant},
 };
 mod base_generator;
+mod client;
 mod configuration;
 mod network;
 mod process;
+use blstrs::G1Projective;
+use client::{Client, PrivateShare};
 use configuration::Configuration;
 use global_lib::{
     config_treatment::{
@@ -15,17 +19,18 @@ use global_lib::{
         fields::Fields,
         result_fields::{DebitCurves, ResultDuration},
     },
-    dec, explicit_log, get_next_message,
+    crypto::{elgamal_encrypt, payload_to_secrets},
+    dec, enc, explicit_log, get_next_message,
     ip_addr::IpV4,
     log,
-    messages::InterfaceCode,
+    messages::{Algo, InterfaceCode},
     process_pool::ProcessPool,
     select,
     settings::{INTERFACE_PORT, LOCAL, LOCAL_IP, MANAGER_PORT, TIMEOUT, VERBOSE, WARM_UP},
     wrap, Evaluation, OpId, Step, Wrapped,
 };
 use network::Network;
-use process::Process;
+use process::{InterfacePoolOutput, NodeOutput, Process};
 use std::fs::File;
 use std::io::Write;
 use tokio::{
@@ -34,14 +39,21 @@ use tokio::{
     sync::mpsc::{channel, Receiver, Sender},
     time::sleep,
 };
+/// Public keys sent by the nodes for each session, with the number of nodes which sent them
+type PublicKeyVotes = HashMap<u8, Vec<(Vec<G1Projective>, u16)>>;
+/// Output of a node holding Feldman sets, with their public keys for each session
+type PublicKeysOutput = (ResultDuration, Option<u16>, Vec<(u8, Vec<G1Projective>)>);
+
 #[derive(Clone)]
 pub struct Interface {
     log: Wrapped<File>,
     network: Network,
-    process_pool: ProcessPool<ResultDuration, ResultDuration>,
+    process_pool: ProcessPool<NodeOutput, InterfacePoolOutput>,
     args: Wrapped<Args>,
     op_id: Wrapped<OpId>,
     cleaning_pool_sender: Wrapped<Option<Sender<u16>>>,
+    clients: Wrapped<HashMap<OpId, Client>>,
+    public_keys: Wrapped<PublicKeyVotes>,
 }
 
 impl Interface {
@@ -66,6 +78,8 @@ impl Interface {
             args: wrap!(Args::default()),
             op_id: wrap!(0),
             cleaning_pool_sender: wrap!(None),
+            clients: wrap!(HashMap::new()),
+            public_keys: wrap!(HashMap::new()),
         };
         log!(interface, "Initializing interface on {interface_ip}");
         (interface, listener)
@@ -78,7 +92,10 @@ impl Interface {
             Connect => add_node ip,
             Output => new_output id,
             NodeReady => new_ready,
-            PoolCleaned => new_pool_cleaned
+            PoolCleaned => new_pool_cleaned,
+            PrivateShare => new_private_share id,
+            Crashed => new_crash id,
+            PublicKeys => new_public_keys id
         );
     }
 
@@ -159,7 +176,13 @@ impl Interface {
         println!("Loading {path}");
         println!("Running with: VERBOSE: {VERBOSE}, LOCAL: {LOCAL}");
         spawn(async move {
-            let args = Args::from_file(path);
+            let args = match Args::from_file(path) {
+                Ok(args) => args,
+                Err(e) => {
+                    eprintln!("{e:?}");
+                    exit(1)
+                }
+            };
             let n = args.get_maximum_network_size();
             log!(self, "Network of size {n}");
             self.args = wrap!(args);
@@ -196,9 +219,21 @@ impl Interface {
     }
 
     async fn setup_operations(&mut self, eval: &Evaluation) {
-        if eval.is_reconstruct() {
+        let fields = self.args.lock().await.get_fields().unwrap().clone();
+        if fields.uses_aba() {
+            // Deals the key whose threshold signatures flip the coins of the binary agreements
+            let mut f = fields;
+            f.set_algo(Algo::Feldman);
+            f.set_step(Step::Sharing);
+            f.remove_crashes();
+            self.start_operation_and_wait_with_fields(f).await;
+        }
+        if eval.needs_shares() {
+            self.public_keys.lock().await.clear();
             let mut f = self.args.lock().await.get_fields().unwrap().clone();
             f.set_step(Step::Sharing);
+            // The crashes are for the evaluated step, the shares must be held by every node
+            f.remove_crashes();
             self.start_operation_and_wait_with_fields(f).await;
         }
     }
@@ -210,9 +245,13 @@ impl Interface {
         self.start_operation_and_wait().await; // To warm up
         for i in 0..hmt {
             explicit_log!(self, "Begining of the operation with hmt={}", i);
-            let result = self.start_operation_and_wait().await;
+            let (result, outputs, rounds, timed_out) = self.start_operation_and_wait().await;
             // self.clean_the_pools(1, None).await;
-            self.args.lock().await.latency_evolve(result).unwrap();
+            let mut args = self.args.lock().await;
+            args.record_outputs(outputs);
+            args.record_rounds(rounds);
+            args.record_timeout(timed_out);
+            args.latency_evolve(result).unwrap();
         }
     }
 
@@ -237,7 +276,13 @@ impl Interface {
     async fn debit_evaluation(&mut self) {
         explicit_log!(self, "Evaluating latency");
         let duration = Duration::from_secs(self.args.lock().await.debit_duration().unwrap() as u64);
-        let b = self.args.lock().await.get_fields().unwrap().batch_size();
+        let b = self
+            .args
+            .lock()
+            .await
+            .get_fields()
+            .unwrap()
+            .outputs_per_operation();
         let f = self.args.lock().await.get_fields().unwrap().clone();
         let mut base_latency = None;
         let hmt = 3;
@@ -273,7 +318,13 @@ impl Interface {
             while timer.elapsed() < duration {
                 let output = receiver.recv().await.unwrap();
                 counter += 1;
-                latency_sum += *output.output;
+                latency_sum += output.output.0;
+                {
+                    let mut args = self.args.lock().await;
+                    args.record_outputs(output.output.1);
+                    args.record_rounds(output.output.2);
+                    args.record_timeout(output.output.3);
+                }
                 explicit_log!(self, "{counter}");
             }
             latency_sum /= counter;
@@ -315,9 +366,17 @@ impl Interface {
         println!("Process is over");
     }
 
-    async fn process_operation_with_fields(&mut self, fields: Fields) {
+    async fn process_operation_with_fields(&mut self, mut fields: Fields) {
         self.inc_op_id().await;
         log!(self, "New op ID: {}", self.op_id().await);
+        if fields.step() == Step::Decrypt {
+            let input = self.decrypt_input(&fields).await;
+            fields.set_step_input(input);
+        }
+        if fields.step() == Step::Reconstruct && fields.private_reconstruct() {
+            let client = Client::new(fields.clone());
+            self.clients.lock().await.insert(self.op_id().await, client);
+        }
         let config = Configuration::new(fields, self.op_id().await, self.network.clone());
         self.process_pool
             .new_task::<Process, Configuration>(self.op_id().await, config)
@@ -325,33 +384,108 @@ impl Interface {
             .unwrap();
     }
 
+    /// Encrypts the input of a decryption to the public keys that t + 1 nodes sent for each session
+    async fn decrypt_input(&self, fields: &Fields) -> Vec<u8> {
+        let input = format!("Operation {}", self.op_id().await).into_bytes();
+        let votes = self.public_keys.lock().await;
+        let ciphertexts = (0..fields.dealers() as u8)
+            .map(|session| {
+                let (keys, _) = votes
+                    .get(&session)
+                    .and_then(|votes| votes.iter().find(|(_, count)| *count > fields.t()))
+                    .expect("No public key to encrypt to");
+                elgamal_encrypt(keys, &input)
+            })
+            .collect::<Vec<_>>();
+        enc!((input, ciphertexts))
+    }
+
     async fn start_operation(&mut self) {
         let f = self.args.lock().await.get_fields().unwrap().clone();
         log!(self, "Starting an operation with fields {f:?}");
         self.process_operation_with_fields(f).await
     }
 
-    pub async fn start_operation_and_wait_with_fields(&mut self, fields: Fields) -> ResultDuration {
+    pub async fn start_operation_and_wait_with_fields(
+        &mut self,
+        fields: Fields,
+    ) -> InterfacePoolOutput {
         let mut receiver = self.process_pool.new_result_redirection().await;
         self.process_operation_with_fields(fields).await;
         *receiver.recv().await.expect("Failed to recv result").output
     }
 
-    async fn start_operation_and_wait(&mut self) -> ResultDuration {
+    async fn start_operation_and_wait(&mut self) -> InterfacePoolOutput {
         let f = match self.args.lock().await.get_fields() {
             Some(fields) => fields.clone(),
             _ => panic!("Config is over"),
         };
         log!(self, "Starting and waiting an operation with fields {f:?}");
         let res = self.start_operation_and_wait_with_fields(f).await;
-        log!(self, "Operation succeed {res}");
+        log!(self, "Operation succeed {res:?}");
         res
     }
 
     async fn new_output(&self, bytes: &[u8], id: OpId) {
         log!(self, "New output on {id}");
-        let result: ResultDuration = dec!(bytes);
-        self.process_pool.send(id, result).await.unwrap()
+        let result: (ResultDuration, Option<u16>) = dec!(bytes);
+        self.send_to_process(id, Some(result)).await
+    }
+
+    /// A node crashed during the operation, it won't produce an output
+    async fn new_crash(&self, _bytes: &[u8], id: OpId) {
+        log!(self, "Node crashed on {id}");
+        self.send_to_process(id, None).await
+    }
+
+    /// Gives the output of a node to the process of its operation, dropping it when the operation
+    /// already timed out
+    async fn send_to_process(&self, id: OpId, output: NodeOutput) {
+        if let Err(e) = self.process_pool.send(id, output).await {
+            log!(self, "Output on {id} after its end: {e:?}");
+        }
+    }
+
+    /// Output of a node which holds Feldman sets, along with their public keys
+    async fn new_public_keys(&self, bytes: &[u8], id: OpId) {
+        log!(self, "New public keys on {id}");
+        let (result, rounds, public_keys): PublicKeysOutput = dec!(bytes);
+        {
+            let mut votes = self.public_keys.lock().await;
+            for (session, keys) in public_keys {
+                let votes = votes.entry(session).or_default();
+                match votes.iter_mut().find(|(k, _)| *k == keys) {
+                    Some((_, count)) => *count += 1,
+                    None => votes.push((keys, 1)),
+                }
+            }
+        }
+        self.send_to_process(id, Some((result, rounds))).await
+    }
+
+    /// Feeds the client of a private reconstruction, the operation ends once it has the secrets
+    async fn new_private_share(&self, bytes: &[u8], id: OpId) {
+        log!(self, "New private share on {id}");
+        let share: PrivateShare = dec!(bytes);
+        let (secrets, client) = {
+            let mut clients = self.clients.lock().await;
+            match clients
+                .get_mut(&id)
+                .and_then(|client| client.new_share(share))
+            {
+                Some(secrets) => (secrets, clients.remove(&id).unwrap()),
+                None => return,
+            }
+        };
+        if let Some(payload) = client.fields().secret() {
+            if secrets != payload_to_secrets(payload, secrets.len()) {
+                // The client gets no output, the run ends on its timeout
+                log!(self, "Secrets of {id} differ from the ones shared");
+                return self.send_to_process(id, None).await;
+            }
+        }
+        log!(self, "Secrets of {id} reconstructed");
+        self.send_to_process(id, Some((0, None))).await
     }
 
     /// Add a node in the network. Bytes contains the port of the node.
@@ -363,7 +497,6 @@ impl Interface {
 
     async fn clean_the_pools(&self, _started: usize, _dur: Option<tokio::time::Duration>) {
         tokio::time::sleep(tokio::time::Duration::from_millis(30_000)).await; // Waiting 30 seconds
-        return;
     }
 
     async fn all_args_consumed(&self) -> bool {
@@ -381,7 +514,7 @@ fn get_managers(path: String) -> Vec<IpV4> {
         .filter(|l| !l.is_empty())
         .map(|s| {
             IpV4::new(
-                IpV4::ip_from_str(&s).expect("Machines ip file invalid"),
+                IpV4::ip_from_str(s).expect("Machines ip file invalid"),
                 MANAGER_PORT,
             )
         })
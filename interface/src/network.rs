@@ -62,7 +62,7 @@ impl WrappedNetwork {
             .expect("Failed to send the ip of the new node")
     }
 
-    async fn generate_nodes(&mut self, managers_ip: &Vec<IpV4>, n: usize) {
+    async fn generate_nodes(&mut self, managers_ip: &[IpV4], n: usize) {
         let nb_manager = managers_ip.len();
         let mut manager_index = 0;
         let mut node_distribution = vec![0; nb_manager];
@@ -95,7 +95,7 @@ impl WrappedNetwork {
         while network.lock().await.ready_counter != n {}
     }
 
-    async fn init_network(network: &Wrapped<Self>, n: u16, managers_ip: &Vec<IpV4>) {
+    async fn init_network(network: &Wrapped<Self>, n: u16, managers_ip: &[IpV4]) {
         let n = n as usize;
         println!("Init the network with {n} nodes");
         network.lock().await.generate_nodes(managers_ip, n).await;
@@ -130,5 +130,5 @@ wrapper_impl!(Network, WrappedNetwork, network,
               broadcast, msg : Vec<u8>, id : OpId, n : Option<usize>
               switch_on_latency
        ;by_name_space,
-    init_network, n : u16, managers_ip : &Vec<IpV4>
+    init_network, n : u16, managers_ip : &[IpV4]
 );
@@ -3,23 +3,33 @@ use global_lib::{
     config_treatment::result_fields::ResultDuration, enc, messages::NodeCommand,
     process::ProcessTrait, task_pool::task::TaskInterface, OpId, Step,
 };
-use std::time::Instant;
-use tokio::sync::mpsc::{channel, Receiver, Sender};
-pub type InterfacePoolOutput = ResultDuration;
+use std::time::SystemTime;
+use tokio::{
+    sync::mpsc::{channel, Receiver, Sender},
+    time::{timeout_at, Instant},
+};
+/// Result of a node and the rounds of its binary agreements, None when it crashed during the operation
+pub type NodeOutput = Option<(ResultDuration, Option<u16>)>;
+/// Duration of the operation, number of nodes which produced an output, most rounds taken by a
+/// binary agreement and whether the operation timed out before the nodes output
+pub type InterfacePoolOutput = (ResultDuration, u16, Option<u16>, bool);
 
 pub struct Process {
-    receiver: Receiver<InterfacePoolOutput>,
+    receiver: Receiver<NodeOutput>,
     sender: TaskInterface<InterfacePoolOutput>,
     config: Configuration,
+    rounds: Option<u16>,
+    deadline: Instant,
+    timed_out: bool,
 }
 
-impl ProcessTrait<Configuration, InterfacePoolOutput, InterfacePoolOutput> for Process {
+impl ProcessTrait<Configuration, NodeOutput, InterfacePoolOutput> for Process {
     /// This function takes in parameter a configuration and then initate the asked operation with the good number of node. This function assumes that the network has enough node to support the operation.
     /// The function returns a sender, the sender is used to tell to the process for a new output. At the end of the operation the process send the result via the given sender
     fn begin(
         config: Configuration,
         self_sender: TaskInterface<InterfacePoolOutput>,
-    ) -> Sender<InterfacePoolOutput> {
+    ) -> Sender<NodeOutput> {
         let (result_sender, self_receiver) = channel(100);
         tokio::spawn(async move {
             Process::start(config, self_sender, self_receiver).await;
@@ -32,22 +42,31 @@ impl Process {
     async fn start(
         config: Configuration,
         sender: TaskInterface<InterfacePoolOutput>,
-        receiver: Receiver<InterfacePoolOutput>,
+        receiver: Receiver<NodeOutput>,
     ) {
+        let deadline = Instant::now() + config.fields().op_timeout();
         let mut process = Process {
             config,
             sender,
             receiver,
+            rounds: None,
+            deadline,
+            timed_out: false,
         };
         process.process().await;
     }
 
     async fn process(&mut self) {
+        let id = self.id();
+        if self.config.fields().step() == Step::Sign {
+            let input = format!("Operation {id}").into_bytes();
+            self.config.fields_mut().set_step_input(input);
+        }
+        self.config.fields_mut().set_op_start(SystemTime::now());
         let fields = self.config.fields();
         println!("{:?} with {fields:?}", fields.step());
         let msg = enc!(Heart, NodeCommand::Process, fields);
-        let id = self.id();
-        let n = self.config.fields().n() as usize;
+        let n = self.config.fields().nodes_involved() as usize;
         self.config.network_mut().broadcast(msg, id, Some(n)).await;
         let result = self.wait_for_outputs().await;
         self.send_result(result).await;
@@ -64,27 +83,66 @@ impl Process {
             .expect("Failed to send output message");
     }
 
+    /// Waits for the outputs of the nodes until the operation times out, the nodes which didn't
+    /// output by then are counted as crashed
     async fn wait_for_outputs(&mut self) -> InterfacePoolOutput {
-        let n = self.config.fields().n();
+        let n = self.config.fields().nodes_involved();
         let t = self.config.fields().t();
         let timer = Instant::now();
         let mut final_result = 0;
+        let mut crashed = 0;
         match self.config.fields().step() {
-            Step::Sharing => {
+            Step::Sharing | Step::Refresh | Step::Reshare | Step::Triples => {
                 for _ in 0..n {
-                    self.receiver.recv().await.expect("Failed to recv");
+                    if self.next_output().await.is_none() {
+                        crashed += 1
+                    }
+                }
+                final_result = timer.elapsed().as_millis() as u64
+            }
+            Step::Reconstruct if self.config.fields().private_reconstruct() => {
+                while self.next_output().await.is_none() && !self.timed_out {
+                    crashed += 1
+                }
+                if self.timed_out {
+                    crashed = n
                 }
                 final_result = timer.elapsed().as_millis() as u64
             }
-            Step::Reconstruct => {
-                for i in 0..n {
-                    self.receiver.recv().await.expect("Failed to recv");
-                    if i == t + 1 {
+            Step::Reconstruct | Step::Beacon | Step::Sign | Step::Decrypt => {
+                let mut outputs = 0;
+                for _ in 0..n {
+                    match self.next_output().await {
+                        Some(_) => outputs += 1,
+                        None => crashed += 1,
+                    }
+                    if outputs == t + 2 && final_result == 0 {
                         final_result = timer.elapsed().as_millis() as u64;
                     }
                 }
+                if self.timed_out && final_result == 0 {
+                    final_result = timer.elapsed().as_millis() as u64;
+                }
             }
         }
-        final_result
+        (final_result, n - crashed, self.rounds, self.timed_out)
+    }
+
+    /// Next output of a node, None once the operation timed out
+    async fn next_output(&mut self) -> NodeOutput {
+        if self.timed_out {
+            return None;
+        }
+        let output = match timeout_at(self.deadline, self.receiver.recv()).await {
+            Ok(output) => output.expect("Failed to recv"),
+            Err(_) => {
+                self.timed_out = true;
+                return None;
+            }
+        };
+        if let Some((_, rounds)) = output {
+            self.rounds = self.rounds.max(rounds);
+        }
+        output
     }
 }
@@ -0,0 +1,129 @@
+use super::state::{AbaAction, AbaState, Round};
+use crate::{
+    break_if_over,
+    crypto::{combine_partial_signs, gen_root, partial_sign, verify_partial_sign},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use blstrs::G2Projective;
+use global_lib::{
+    enc, init_message,
+    messages::{AbaCommand, NameSpace},
+    try_dec, Wrapped,
+};
+use std::{collections::HashMap, io::Write};
+
+type CoinShares = HashMap<u16, Vec<G2Projective>>;
+
+pub async fn listen_at(node: Wrapped<Node>) {
+    let (n, t, id, index, silent) = {
+        let node = node.lock().await;
+        let config = node.agreement_config();
+        let (n, t) = (config.n() as usize, config.t() as usize);
+        (n, t, node.op_id(), node.index(), node.is_byz())
+    };
+    let mut states: HashMap<u16, AbaState> = HashMap::new();
+    let mut coin_shares: HashMap<(u16, Round), CoinShares> = HashMap::new();
+    let channels = [NodeMessage::AbaSenderConst, NodeMessage::AbaInputConst];
+    let mut receiver = node.lock().await.subscribe_multiple(&channels);
+    loop {
+        let msg = break_if_over!(receiver);
+        let (instance, actions) = match msg {
+            NodeMessage::AbaInput(instance, est) => {
+                let state = states
+                    .entry(instance)
+                    .or_insert_with(|| AbaState::new(n, t));
+                (instance, state.input(est))
+            }
+            NodeMessage::AbaSender(from, bytes) => {
+                // The votes are counted by sender, a node that doesn't vote can't count as one
+                if from as usize >= n {
+                    continue;
+                }
+                let instance: u16 = try_dec!(bytes[1..3], continue);
+                let state = states
+                    .entry(instance)
+                    .or_insert_with(|| AbaState::new(n, t));
+                let actions = match AbaCommand::from_checked(bytes[0]) {
+                    Some(AbaCommand::BVal) => {
+                        let (round, b): (Round, bool) = try_dec!(bytes[3..], continue);
+                        state.new_bval(from, round, b)
+                    }
+                    Some(AbaCommand::Aux) => {
+                        let (round, b): (Round, bool) = try_dec!(bytes[3..], continue);
+                        state.new_aux(from, round, b)
+                    }
+                    Some(AbaCommand::Coin) => {
+                        let (round, partial): (Round, Vec<G2Projective>) =
+                            try_dec!(bytes[3..], continue);
+                        let shares = coin_shares.entry((instance, round)).or_default();
+                        if shares.len() > t || shares.contains_key(&from) {
+                            continue;
+                        }
+                        let mut node = node.lock().await;
+                        let (config, comm) = (node.agreement_config(), node.coin_set().get_comm());
+                        let message = AbaState::coin_message(id, instance, round);
+                        if !verify_partial_sign(config, comm, from, &message, &partial) {
+                            node.log(&format!("Dropped the invalid coin share of {from}"));
+                            continue;
+                        }
+                        shares.insert(from, partial);
+                        if shares.len() <= t {
+                            continue;
+                        }
+                        state.new_coin(round, coin(&combine_partial_signs(config, shares)))
+                    }
+                    Some(AbaCommand::Term) => {
+                        let b: bool = try_dec!(bytes[3..], continue);
+                        state.new_term(from, b)
+                    }
+                    None => continue,
+                };
+                (instance, actions)
+            }
+            _ => panic!("Unexpected message"),
+        };
+        for action in actions {
+            perform(&node, instance, action, index, silent).await;
+        }
+    }
+}
+
+async fn perform(node: &Wrapped<Node>, instance: u16, action: AbaAction, index: u16, silent: bool) {
+    let (command, payload) = match action {
+        AbaAction::Output(b, round) => {
+            node.lock().await.record_rounds(round + 1);
+            Node::wait_and_send(node, NodeMessage::AbaOutput(instance, b, round)).await;
+            return;
+        }
+        _ if silent => return,
+        AbaAction::BVal(round, b) => (AbaCommand::BVal, enc!((round, b))),
+        AbaAction::Aux(round, b) => (AbaCommand::Aux, enc!((round, b))),
+        AbaAction::Coin(round) => {
+            let node = node.lock().await;
+            let message = AbaState::coin_message(node.op_id(), instance, round);
+            let partial = partial_sign(node.coin_set().get(index), &message);
+            (AbaCommand::Coin, enc!((round, partial)))
+        }
+        AbaAction::Term(b) => (AbaCommand::Term, enc!(b)),
+    };
+    let mut msg = init_message(NameSpace::Aba, command);
+    enc!(instance, msg);
+    msg.extend(payload);
+    node.lock().await.broadcast_to_voters(msg, true).await;
+}
+
+/// The threshold signature is unique, every node draws the same coin from it
+fn coin(signs: &[G2Projective]) -> bool {
+    gen_root(&signs[0].to_compressed())[0] & 1 == 1
+}
+
+pub async fn aba_share(node: Wrapped<Node>) {
+    let mut receiver = node.lock().await.subscribe(NodeMessage::AbaOutputConst);
+    let est = node.lock().await.index() % 2 == 0;
+    Node::wait_and_send(&node, NodeMessage::AbaInput(0, est)).await;
+    if let Some(NodeMessage::AbaOutput(_, b, round)) = receiver.recv().await {
+        log!(node, "Agreed on {b} after {} rounds", round + 1);
+        Node::output(node);
+    }
+}
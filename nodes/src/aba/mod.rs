@@ -0,0 +1,4 @@
+mod messages_receiver;
+mod state;
+
+pub use messages_receiver::{aba_share, listen_at as aba_listen};
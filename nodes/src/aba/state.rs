@@ -0,0 +1,233 @@
+use global_lib::{enc, OpId};
+use std::collections::{HashMap, HashSet};
+
+pub type Round = u16;
+
+pub enum AbaAction {
+    BVal(Round, bool),
+    Aux(Round, bool),
+    Coin(Round),
+    Term(bool),
+    Output(bool, Round),
+}
+
+// Binary agreement of Mostefaoui, Moumen and Raynal, with a termination gadget
+pub struct AbaState {
+    n: usize,
+    t: usize,
+    round: Round,
+    est: Option<bool>,
+    bvals: HashMap<(Round, bool), HashSet<u16>>,
+    bval_sent: HashSet<(Round, bool)>,
+    bin_values: HashMap<Round, Vec<bool>>,
+    aux: HashMap<Round, HashMap<u16, bool>>,
+    aux_sent: HashSet<Round>,
+    vals: Option<Vec<bool>>, // Values of the n - t aux of the current round, waiting for the coin
+    coins: HashMap<Round, bool>,
+    terms: [HashSet<u16>; 2],
+    term_sent: bool,
+    decided: Option<Round>,
+    done: bool,
+}
+
+impl AbaState {
+    pub fn new(n: usize, t: usize) -> Self {
+        Self {
+            n,
+            t,
+            round: 0,
+            est: None,
+            bvals: HashMap::new(),
+            bval_sent: HashSet::new(),
+            bin_values: HashMap::new(),
+            aux: HashMap::new(),
+            aux_sent: HashSet::new(),
+            vals: None,
+            coins: HashMap::new(),
+            terms: [HashSet::new(), HashSet::new()],
+            term_sent: false,
+            decided: None,
+            done: false,
+        }
+    }
+
+    // The coin of a round is drawn from the threshold signature of this message, no one knows it
+    // before t + 1 nodes reached the round
+    pub fn coin_message(id: OpId, instance: u16, round: Round) -> Vec<u8> {
+        enc!((id, instance, round))
+    }
+
+    pub fn input(&mut self, est: bool) -> Vec<AbaAction> {
+        if self.est.is_some() {
+            return Vec::new();
+        }
+        self.est = Some(est);
+        self.progress()
+    }
+
+    pub fn new_bval(&mut self, from: u16, round: Round, b: bool) -> Vec<AbaAction> {
+        let senders = self.bvals.entry((round, b)).or_default();
+        senders.insert(from);
+        let count = senders.len();
+        let mut actions = Vec::new();
+        if count > self.t && self.bval_sent.insert((round, b)) {
+            actions.push(AbaAction::BVal(round, b));
+        }
+        if count > 2 * self.t {
+            let bin_values = self.bin_values.entry(round).or_default();
+            if !bin_values.contains(&b) {
+                bin_values.push(b);
+            }
+        }
+        actions.append(&mut self.progress());
+        actions
+    }
+
+    pub fn new_aux(&mut self, from: u16, round: Round, b: bool) -> Vec<AbaAction> {
+        self.aux.entry(round).or_default().entry(from).or_insert(b);
+        self.progress()
+    }
+
+    pub fn new_coin(&mut self, round: Round, coin: bool) -> Vec<AbaAction> {
+        self.coins.insert(round, coin);
+        self.progress()
+    }
+
+    pub fn new_term(&mut self, from: u16, b: bool) -> Vec<AbaAction> {
+        let mut actions = Vec::new();
+        if self.done {
+            return actions;
+        }
+        let senders = &mut self.terms[b as usize];
+        senders.insert(from);
+        let count = senders.len();
+        if count > self.t && !self.term_sent {
+            self.term_sent = true;
+            actions.push(AbaAction::Term(b));
+        }
+        if count > 2 * self.t {
+            self.done = true;
+            actions.push(AbaAction::Output(b, self.decided.unwrap_or(self.round)));
+        }
+        actions
+    }
+
+    fn progress(&mut self) -> Vec<AbaAction> {
+        let mut actions = Vec::new();
+        if self.done || self.est.is_none() {
+            return actions;
+        }
+        loop {
+            let round = self.round;
+            let est = self.est.unwrap();
+            if self.bval_sent.insert((round, est)) {
+                actions.push(AbaAction::BVal(round, est));
+            }
+            let bin_values = match self.bin_values.get(&round) {
+                Some(bin_values) => bin_values.clone(),
+                None => break,
+            };
+            if self.aux_sent.insert(round) {
+                actions.push(AbaAction::Aux(round, bin_values[0]));
+            }
+            if self.vals.is_none() {
+                let mut vals = Vec::new();
+                let mut count = 0;
+                for b in self
+                    .aux
+                    .get(&round)
+                    .into_iter()
+                    .flat_map(|aux| aux.values())
+                {
+                    if bin_values.contains(b) {
+                        count += 1;
+                        if !vals.contains(b) {
+                            vals.push(*b);
+                        }
+                    }
+                }
+                if count < self.n - self.t {
+                    break;
+                }
+                self.vals = Some(vals);
+                actions.push(AbaAction::Coin(round));
+            }
+            let coin = match self.coins.get(&round) {
+                Some(coin) => *coin,
+                None => break,
+            };
+            match self.vals.take().unwrap()[..] {
+                [v] => {
+                    self.est = Some(v);
+                    if v == coin && self.decided.is_none() {
+                        self.decided = Some(round);
+                        if !self.term_sent {
+                            self.term_sent = true;
+                            actions.push(AbaAction::Term(v));
+                        }
+                    }
+                }
+                _ => self.est = Some(coin),
+            }
+            self.round += 1;
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs the agreement between honest nodes delivering every message at once, the coin of a
+    /// round being flipped once t + 1 nodes asked for it. Returns the output of every node.
+    fn run(inputs: &[bool], coin: impl Fn(Round) -> bool) -> Vec<Option<(bool, Round)>> {
+        let (n, t) = (inputs.len(), (inputs.len() - 1) / 3);
+        let mut states = (0..n).map(|_| AbaState::new(n, t)).collect::<Vec<_>>();
+        let mut outputs = vec![None; n];
+        let mut coin_requests: HashMap<Round, usize> = HashMap::new();
+        let mut queue = std::collections::VecDeque::new();
+        for (i, b) in inputs.iter().enumerate() {
+            queue.extend(states[i].input(*b).into_iter().map(|a| (i, a)));
+        }
+        while let Some((i, action)) = queue.pop_front() {
+            let from = i as u16;
+            let mut deliver = |f: &dyn Fn(&mut AbaState) -> Vec<AbaAction>| {
+                for (j, state) in states.iter_mut().enumerate() {
+                    queue.extend(f(state).into_iter().map(|a| (j, a)));
+                }
+            };
+            match action {
+                AbaAction::BVal(round, b) => deliver(&|s| s.new_bval(from, round, b)),
+                AbaAction::Aux(round, b) => deliver(&|s| s.new_aux(from, round, b)),
+                AbaAction::Term(b) => deliver(&|s| s.new_term(from, b)),
+                AbaAction::Coin(round) => {
+                    let requests = coin_requests.entry(round).or_default();
+                    *requests += 1;
+                    if *requests == t + 1 {
+                        deliver(&|s| s.new_coin(round, coin(round)))
+                    }
+                }
+                AbaAction::Output(b, round) => outputs[i] = Some((b, round)),
+            }
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_aba_unanimous_input() {
+        for b in [false, true] {
+            let outputs = run(&[b; 4], |round| round % 2 == 0);
+            assert!(outputs.iter().all(|o| o.map(|(v, _)| v) == Some(b)));
+        }
+    }
+
+    #[test]
+    fn test_aba_split_input() {
+        let outputs = run(&[true, false, true, false, true, false, true], |round| {
+            round % 3 != 0
+        });
+        let decided = outputs[0].expect("No output").0;
+        assert!(outputs.iter().all(|o| o.map(|(v, _)| v) == Some(decided)));
+    }
+}
@@ -0,0 +1,93 @@
+use super::state::{AcsAction, AcsState};
+use crate::{
+    break_if_over,
+    broadcast::broadcast_message_types::BroadcastMessageType,
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use global_lib::{enc, Wrapped};
+use std::io::Write;
+use tokio::sync::mpsc::{Receiver, Sender};
+
+// Reliably broadcasts every proposal, then runs one binary agreement per
+// node to decide which broadcasts end up in the common subset
+pub async fn listen_at(node: Wrapped<Node>) {
+    let mut state = {
+        let node = node.lock().await;
+        let config = node.agreement_config();
+        AcsState::new(config.n(), config.t())
+    };
+    let channels = [
+        NodeMessage::AcsInputConst,
+        NodeMessage::BroadcastAcsConst,
+        NodeMessage::AbaOutputConst,
+    ];
+    let mut receiver = node.lock().await.subscribe_multiple(&channels);
+    loop {
+        let msg = break_if_over!(receiver);
+        if state.is_done() {
+            continue;
+        }
+        let actions = match msg {
+            NodeMessage::AcsInput(proposal) => {
+                let mut node = node.lock().await;
+                node.reliable_broadcast(BroadcastMessageType::Acs, proposal)
+                    .await;
+                continue;
+            }
+            NodeMessage::BroadcastAcs(origin, proposal) => state.new_proposal(origin, proposal),
+            NodeMessage::AbaOutput(instance, b, _) => state.new_decision(instance, b),
+            _ => panic!("Unexpected message"),
+        };
+        for action in actions {
+            let msg = match action {
+                AcsAction::AbaInput(j, b) => NodeMessage::AbaInput(j, b),
+                AcsAction::Output(subset) => NodeMessage::AcsOutput(subset),
+            };
+            Node::wait_and_send(&node, msg).await;
+        }
+    }
+}
+
+pub async fn acs_share(node: Wrapped<Node>) {
+    let mut receiver = node.lock().await.subscribe(NodeMessage::AcsOutputConst);
+    if !node.lock().await.is_byz() {
+        let proposal = enc!(node.lock().await.index());
+        Node::wait_and_send(&node, NodeMessage::AcsInput(proposal)).await;
+    }
+    if let Some(NodeMessage::AcsOutput(subset)) = receiver.recv().await {
+        log!(node, "Agreed on a subset of {} proposals", subset.len());
+        Node::output(node);
+    }
+}
+
+/// Agrees on the sessions of an operation, a node proposes a session once it is over on its side.
+/// The sessions have no content to broadcast, whether they are over is known locally.
+pub async fn agree_on_sessions(node: Wrapped<Node>, over: Receiver<u16>, agreed: Sender<Vec<u16>>) {
+    if let Some(subset) = agree_on(&node, over).await {
+        log!(node, "Agreed on the sessions {subset:?}");
+        let _ = agreed.send(subset).await;
+        Node::output(node);
+    }
+}
+
+/// Agrees on a subset of the instances the nodes vote for, a node votes for an instance once it
+/// is valid on its side
+pub async fn agree_on(node: &Wrapped<Node>, mut votes: Receiver<u16>) -> Option<Vec<u16>> {
+    let mut receiver = node.lock().await.subscribe(NodeMessage::AcsOutputConst);
+    let proposer = node.clone();
+    tokio::spawn(async move {
+        while let Some(instance) = votes.recv().await {
+            vote(&proposer, instance).await;
+        }
+    });
+    match receiver.recv().await {
+        Some(NodeMessage::AcsOutput(subset)) => Some(subset.into_iter().map(|(j, _)| j).collect()),
+        _ => None,
+    }
+}
+
+/// Votes for an instance of the agreement, the subset is then output on `AcsOutput`
+pub async fn vote(node: &Wrapped<Node>, instance: u16) {
+    Node::wait_and_send(node, NodeMessage::BroadcastAcs(instance, Vec::new())).await;
+}
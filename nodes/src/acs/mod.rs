@@ -0,0 +1,6 @@
+mod messages_receiver;
+mod state;
+
+pub use messages_receiver::{
+    acs_share, agree_on, agree_on_sessions, listen_at as acs_listen, vote,
+};
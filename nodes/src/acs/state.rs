@@ -0,0 +1,144 @@
+use std::collections::{HashMap, HashSet};
+
+pub enum AcsAction {
+    AbaInput(u16, bool),
+    Output(Vec<(u16, Vec<u8>)>),
+}
+
+// Common subset of Ben-Or, Kelmer and Rabin: one reliable broadcast and one binary agreement per
+// node, the subset being the broadcasts whose agreement decided true
+pub struct AcsState {
+    n: u16,
+    t: u16,
+    proposals: HashMap<u16, Vec<u8>>,
+    aba_started: HashSet<u16>,
+    decisions: HashMap<u16, bool>,
+    done: bool,
+}
+
+impl AcsState {
+    pub fn new(n: u16, t: u16) -> Self {
+        Self {
+            n,
+            t,
+            proposals: HashMap::new(),
+            aba_started: HashSet::new(),
+            decisions: HashMap::new(),
+            done: false,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn new_proposal(&mut self, origin: u16, proposal: Vec<u8>) -> Vec<AcsAction> {
+        let mut actions = Vec::new();
+        if self.done {
+            return actions;
+        }
+        self.proposals.insert(origin, proposal);
+        if self.aba_started.insert(origin) {
+            actions.push(AcsAction::AbaInput(origin, true));
+        }
+        actions.extend(self.output());
+        actions
+    }
+
+    pub fn new_decision(&mut self, instance: u16, b: bool) -> Vec<AcsAction> {
+        let mut actions = Vec::new();
+        if self.done {
+            return actions;
+        }
+        self.decisions.insert(instance, b);
+        if self.decisions.values().filter(|b| **b).count() >= (self.n - self.t) as usize {
+            for j in 0..self.n {
+                if self.aba_started.insert(j) {
+                    actions.push(AcsAction::AbaInput(j, false));
+                }
+            }
+        }
+        actions.extend(self.output());
+        actions
+    }
+
+    fn output(&mut self) -> Option<AcsAction> {
+        if self.decisions.len() != self.n as usize
+            || self
+                .decisions
+                .iter()
+                .any(|(j, b)| *b && !self.proposals.contains_key(j))
+        {
+            return None;
+        }
+        let mut subset = self
+            .decisions
+            .iter()
+            .filter(|(_, b)| **b)
+            .map(|(j, _)| (*j, self.proposals.remove(j).unwrap()))
+            .collect::<Vec<_>>();
+        subset.sort_by_key(|(j, _)| *j);
+        self.done = true;
+        Some(AcsAction::Output(subset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs the common subset with the broadcasts of `delivered` only, each agreement deciding
+    /// whether a majority of the nodes asked for true. Returns the output of every node.
+    fn run(n: u16, delivered: &[u16]) -> Vec<Option<Vec<u16>>> {
+        let t = (n - 1) / 3;
+        let mut states = (0..n).map(|_| AcsState::new(n, t)).collect::<Vec<_>>();
+        let mut outputs = vec![None; n as usize];
+        let mut inputs: HashMap<u16, Vec<bool>> = HashMap::new();
+        let mut queue = std::collections::VecDeque::new();
+        for origin in delivered {
+            for (i, state) in states.iter_mut().enumerate() {
+                let proposal = vec![*origin as u8];
+                queue.extend(
+                    state
+                        .new_proposal(*origin, proposal)
+                        .into_iter()
+                        .map(|a| (i, a)),
+                );
+            }
+        }
+        while let Some((i, action)) = queue.pop_front() {
+            match action {
+                AcsAction::AbaInput(j, b) => {
+                    let votes = inputs.entry(j).or_default();
+                    votes.push(b);
+                    if votes.len() == n as usize {
+                        let decision = votes.iter().filter(|b| **b).count() > (n / 2) as usize;
+                        for (k, state) in states.iter_mut().enumerate() {
+                            queue.extend(
+                                state.new_decision(j, decision).into_iter().map(|a| (k, a)),
+                            );
+                        }
+                    }
+                }
+                AcsAction::Output(subset) => {
+                    outputs[i] = Some(subset.into_iter().map(|(j, _)| j).collect());
+                }
+            }
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_acs_all_proposals() {
+        let outputs = run(4, &[0, 1, 2, 3]);
+        assert!(outputs
+            .iter()
+            .all(|o| o.as_deref() == Some(&[0, 1, 2, 3][..])));
+    }
+
+    #[test]
+    fn test_acs_missing_proposal() {
+        let outputs = run(4, &[3, 0, 2]);
+        assert!(outputs.iter().all(|o| o.as_deref() == Some(&[0, 2, 3][..])));
+    }
+}
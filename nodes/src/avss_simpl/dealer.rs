@@ -7,7 +7,8 @@ use super::crypto_messages::BroadcastReceiv;
 use crate::{
     broadcast::broadcast_message_types::BroadcastMessageType,
     crypto::{
-        compute_comm_and_shares, encode_shares, gen_root, is_valid_sign, Commitment, Share, Sign,
+        compute_comm_and_shares, dealt_share, encode_shares, gen_root, is_valid_sign, Commitment,
+        Share, Sign,
     },
     node::{node::Node, node_message::NodeMessage},
     panic_if_over,
@@ -18,34 +19,21 @@ type ShareMap = HashMap<u16, Share>;
 
 async fn send_first_message(node: &Wrapped<Node>) -> (ShareMap, Commitment) {
     let mut node = node.lock().await;
-    let (comm, mut output, secrets) = compute_comm_and_shares(node.config());
+    let (comm, output, secrets) = compute_comm_and_shares(node.config());
     node.set_secrets(secrets);
     let config = node.config();
     let mut shares = HashMap::<u16, Share>::new();
     let comm_parsed = enc!(comm);
-    let cor = config.n() - config.dealer_corruption();
-    let empty_share = Share::empty(config.batch_size() as usize);
-    let messages = (0..config.n())
-        .rev()
-        .map(|i| {
-            let share = if i >= cor {
-                let mut share = empty_share.clone();
-                share.set_index(i);
-                share
-            } else {
-                output.pop().unwrap()
-            };
-            let mut buf = enc!(AvssSimpl, AvssSimplCommand::Share, share);
+    let messages = output
+        .into_iter()
+        .take(config.n() as usize)
+        .enumerate()
+        .map(|(i, share)| {
+            let dealt = dealt_share(config, i as u16, share.clone(), None);
+            let mut buf = enc!(AvssSimpl, AvssSimplCommand::Share, dealt);
             buf.extend(&comm_parsed);
-            shares.insert(
-                i,
-                if i >= cor {
-                    output.pop().unwrap()
-                } else {
-                    share
-                },
-            );
-            (i as usize, buf)
+            shares.insert(i as u16, share);
+            (i, buf)
         })
         .collect::<Vec<(usize, Vec<u8>)>>();
     for (i, msg) in messages {
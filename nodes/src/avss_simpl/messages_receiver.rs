@@ -5,11 +5,11 @@ use super::{
 use crate::{
     avss_simpl::crypto_messages::BroadcastReceiv,
     break_if_over,
-    crypto::{Commitment, Share},
+    crypto::{Commitment, Share, Sign},
     log,
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::AvssSimplCommand, select, Step, Wrapped};
+use global_lib::{messages::AvssSimplCommand, select, try_dec, Wrapped};
 use std::io::Write;
 
 pub async fn listen_at(node: Wrapped<Node>) {
@@ -24,7 +24,10 @@ pub async fn listen_at(node: Wrapped<Node>) {
                 log!(
                     node,
                     "Avss Simpl, new message: {:?}",
-                    AvssSimplCommand::from(bytes_message[0])
+                    bytes_message
+                        .first()
+                        .copied()
+                        .and_then(AvssSimplCommand::from_checked)
                 );
                 let node = node.clone();
                 handlers.push(select!(AvssSimplCommand, bytes_message, node,
@@ -50,17 +53,18 @@ pub async fn avss_simpl_share(node: Wrapped<Node>) {
 }
 
 async fn share_receiv(node: Wrapped<Node>, bytes: &[u8]) {
-    let (share, comm): (Share, Commitment) = dec!(bytes);
+    let (share, comm): (Share, Commitment) = try_dec!(bytes);
     first_receiv(node, comm, share).await;
 }
 
 async fn new_sign(node: Wrapped<Node>, bytes: &[u8]) {
-    let (i, sign) = dec!(bytes, (u16, Sign));
+    let (i, sign): (u16, Sign) = try_dec!(bytes);
     let _ = Node::try_wait_and_send(&node, NodeMessage::AvssSimplDealerMessage(i, sign)).await;
 }
 
 async fn new_share(node: Wrapped<Node>, bytes: &[u8]) {
-    let msg = NodeMessage::AvssSimplExtShare(dec!(bytes, Share));
+    let (share, rand_comms) = try_dec!(bytes);
+    let msg = NodeMessage::AvssSimplExtShare(share, rand_comms);
     Node::wait_and_send(&node, msg).await;
 }
 
@@ -69,13 +73,13 @@ async fn broadcast_reception_handler(node: Wrapped<Node>) {
         .lock()
         .await
         .push_handler(tokio::spawn(async move {
-            if node.lock().await.step() == Step::Reconstruct {
+            if !node.lock().await.step().is_sharing() {
                 return;
             }
             let channel = NodeMessage::BroadcastAvssSimplConst;
             let mut receiver = node.lock().await.subscribe(channel);
             let broadcast_recv: BroadcastReceiv = match receiver.recv().await.unwrap() {
-                NodeMessage::BroadcastAvssSimpl(b) => dec!(b),
+                NodeMessage::BroadcastAvssSimpl(b) => try_dec!(b, return Node::abort(node)),
                 _ => panic!("Unexpected message"),
             };
             verify_and_output(node, broadcast_recv).await
@@ -2,7 +2,7 @@ use crate::{
     crypto::{
         crypto_lib::vss::{common::low_deg_test, ni_vss::encryption::dec_chunks},
         data_structures::{encryption::Encryption, keypair::PublicKey},
-        gen_root, verify_encryption, Commitment, Share,
+        decrypt_rand_comms, enc_rand_comms, gen_root, verify_encryption, Commitment, Share,
     },
     log,
     node::{configuration::Configuration, node::Node},
@@ -96,15 +96,25 @@ pub async fn verify_and_output(node: Wrapped<Node>, data: BroadcastReceiv) {
             &node.get_all_pkey(),
             &missing_coms,
         ));
-        missing.iter().for_each(|i| shares_set[*i] = true);
+        for (pos, i) in missing.iter().enumerate() {
+            shares_set[*i] = true;
+            node.set_mut()
+                .set_enc_rand_comms(*i as u16, enc_rand_comms(&encs, pos));
+        }
         if !node.has_share() && missing.contains(&node.uindex()) {
             let i: usize = missing.iter().position(|i| node.uindex() == *i).unwrap();
             let shares: Vec<blstrs::Scalar> = encs
                 .iter()
                 .map(|e| dec_chunks(&e.ciphertext, *node.my_decrypt_skey(), i).unwrap())
                 .collect();
+            let rand_comms = decrypt_rand_comms(
+                node.config(),
+                &enc_rand_comms(&encs, i),
+                node.my_decrypt_skey(),
+            );
             let index = node.index();
             let l = shares.len();
+            node.set_mut().set_rand_comms(rand_comms);
             node.save_share(Share::new(index, shares, vec![blstrs::Scalar::from(0); l]))
                 .await;
         }
@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 
+use blstrs::G1Projective;
 use global_lib::{enc, messages::AvssSimplCommand, wrap, Wrapped};
 
 use crate::{
     break_if_over,
-    crypto::{interpolate, Share},
+    crypto::{interpolate_with_rand_comms, verify_decrypted_share, RandComm, Share},
+    log,
     node::{node::Node, node_message::NodeMessage},
 };
 use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::io::Write;
 
 pub async fn reconstruct(node: Wrapped<Node>) {
     let cloned_node = node.clone();
@@ -17,44 +20,68 @@ pub async fn reconstruct(node: Wrapped<Node>) {
     if node.is_byz() {
         return;
     }
-    let msg = enc!(AvssSimpl, AvssSimplCommand::NewShare, node.my_share());
+    let msg = enc!(
+        AvssSimpl,
+        AvssSimplCommand::NewShare,
+        (node.my_share(), node.set().get_rand_comms())
+    );
     node.broadcast(msg, false).await;
 }
 
 async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
-    let my_share = node.lock().await.my_share().clone();
-    let enough = 2 * node.lock().await.t() + 1;
-    let mut share_counter = enough - 1;
+    let (my_share, my_rand_comms) = {
+        let node = node.lock().await;
+        (node.my_share().clone(), node.set().get_rand_comms().clone())
+    };
+    let enough = node.lock().await.config().get_threshold() as u16;
     let set = wrap!(HashMap::<u16, Share>::new());
+    let rand_set = wrap!(HashMap::<u16, Vec<G1Projective>>::new());
+    if let Some(rand_comms) = my_rand_comms {
+        rand_set.lock().await.insert(
+            my_share.index(),
+            rand_comms.iter().map(|(r, _)| *r).collect(),
+        );
+    }
     set.lock().await.insert(my_share.index(), my_share);
     loop {
         let msg = break_if_over!(receiver);
-        if share_counter == 0 {
-            continue;
-        }
-        share_counter -= 1;
         let set = set.clone();
+        let rand_set = rand_set.clone();
         let node = node.clone();
-        let comm = node.lock().await.get_comm().clone();
         tokio::spawn(async move {
             match msg {
-                NodeMessage::AvssSimplExtShare(share) => {
+                NodeMessage::AvssSimplExtShare(share, rand_comms) => {
                     let i = share.index();
-                    if !comm.verify_on(0, i as usize, &share.get(0))
-                        || set.lock().await.contains_key(&i)
-                    {
-                        panic!("I Failed to recv a share")
-                    }
+                    let valid = verify_share(&*node.lock().await, &share, &rand_comms);
                     let mut set = set.lock().await;
+                    if !valid || set.contains_key(&i) || set.len() as u16 == enough {
+                        if !valid {
+                            log!(node, "Dropped the invalid share of {i}");
+                        }
+                        return;
+                    }
+                    let mut rand_set = rand_set.lock().await;
                     set.insert(i, share);
+                    if let Some(rand_comms) = rand_comms {
+                        rand_set.insert(i, rand_comms.into_iter().map(|(r, _)| r).collect());
+                    }
                     if set.len() as u16 == enough {
-                        {
+                        let interpolated = {
                             let node = node.lock().await;
-                            if !interpolate(node.config(), &set, node.get_secrets()) {
-                                panic!("FAILED TO INTERPOLATE")
-                            }
+                            interpolate_with_rand_comms(
+                                node.config(),
+                                node.get_comm(),
+                                &set,
+                                &rand_set,
+                                node.get_secrets(),
+                            )
+                        };
+                        if interpolated {
+                            Node::output(node)
+                        } else {
+                            log!(node, "Failed to interpolate the secrets");
+                            Node::abort(node)
                         }
-                        Node::output(node)
                     }
                 }
                 _ => {
@@ -64,3 +91,26 @@ async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<
         });
     }
 }
+
+/// A share decrypted from the dual threshold broadcast comes without its randomness, only with the
+/// h^r of it proven against the encryption of the dealer.
+fn verify_share(node: &Node, share: &Share, rand_comms: &Option<Vec<RandComm>>) -> bool {
+    let comm = node.get_comm();
+    match rand_comms {
+        Some(rand_comms) => {
+            node.set()
+                .get_enc_rand_comms(share.index())
+                .is_some_and(|enc_rand_comms| {
+                    verify_decrypted_share(
+                        node.config(),
+                        comm,
+                        share,
+                        &node.get_specific_key(share.index()).c_key(),
+                        enc_rand_comms,
+                        rand_comms,
+                    )
+                })
+        }
+        None => share.only_share().batch().len() == comm.batch_size() && comm.verify(share),
+    }
+}
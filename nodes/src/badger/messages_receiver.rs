@@ -5,7 +5,7 @@ use crate::{
     log,
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::BadgerCommand, select, Step, Wrapped};
+use global_lib::{messages::BadgerCommand, select, try_dec, Wrapped};
 use std::io::Write;
 
 pub async fn listen_at(node: Wrapped<Node>) {
@@ -38,13 +38,13 @@ pub async fn badger_share(node: Wrapped<Node>) {
 }
 
 async fn new_reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
-    let share: Share = dec!(bytes);
+    let share: Share = try_dec!(bytes);
     let _ = Node::wait_and_send(&node, NodeMessage::BadgerReconstructShare(share)).await;
 }
 
 fn broadcast_reception_handler(node: Wrapped<Node>) {
     tokio::spawn(async move {
-        if node.lock().await.step() == Step::Reconstruct {
+        if !node.lock().await.step().is_sharing() {
             return;
         }
         log!(node, "Will wait for broadcast to complete");
@@ -52,7 +52,7 @@ fn broadcast_reception_handler(node: Wrapped<Node>) {
         let mut receiver = node.lock().await.subscribe(channel);
         log!(node, "Waiting for broadcast...");
         let broadcast_recv: BroadcastReceiv = match receiver.recv().await.unwrap() {
-            NodeMessage::BroadcastBadger(b) => dec!(b),
+            NodeMessage::BroadcastBadger(b) => try_dec!(b, return Node::abort(node)),
             _ => panic!("Unexpected message"),
         };
         log!(node, "Broadcast phase has ended, decrypting shares.");
@@ -1,10 +1,11 @@
 use global_lib::{enc, messages::BadgerCommand, wrap, Wrapped};
 use notifier_hub::notifier::MessageReceiver as Receiver;
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Write};
 
 use crate::{
     break_if_over,
     crypto::{interpolate, Share},
+    log,
     node::{node::Node, node_message::NodeMessage},
 };
 
@@ -22,16 +23,14 @@ pub async fn reconstruct(node: Wrapped<Node>) {
 
 async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
     let my_share = node.lock().await.my_share().clone();
-    let enough = node.lock().await.l() + 1;
-    let mut share_counter = enough - 1;
+    let enough = node.lock().await.config().get_threshold() as u16;
     let set = wrap!(HashMap::<u16, Share>::new());
     set.lock().await.insert(my_share.index(), my_share);
     loop {
         let msg = break_if_over!(receiver);
-        if share_counter == 0 {
+        if set.lock().await.len() as u16 == enough {
             continue;
         }
-        share_counter -= 1;
         let set = set.clone();
         let node = node.clone();
         let comm = node.lock().await.get_comm().clone();
@@ -39,17 +38,25 @@ async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<
             match msg {
                 NodeMessage::BadgerReconstructShare(share) => {
                     let i = share.index();
-                    if !comm.verify_on(0, i as usize, &share.get(0))
-                        || set.lock().await.contains_key(&i)
-                    {
-                        panic!("I Failed to recv a share")
-                    }
+                    let valid =
+                        i < node.lock().await.n() && comm.verify_on(0, i as usize, &share.get(0));
                     let mut set = set.lock().await;
+                    if !valid || set.contains_key(&i) || set.len() as u16 == enough {
+                        if !valid {
+                            log!(node, "Dropped the invalid share of {i}");
+                        }
+                        return;
+                    }
                     set.insert(i, share);
                     if set.len() as u16 == enough {
                         {
                             let node = node.lock().await;
-                            if !interpolate(node.config(), &set, node.get_secrets()) {
+                            if !interpolate(
+                                node.config(),
+                                node.get_comm(),
+                                &set,
+                                node.get_secrets(),
+                            ) {
                                 panic!("FAILED TO INTERPOLATE")
                             }
                         }
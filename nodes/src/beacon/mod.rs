@@ -0,0 +1,36 @@
+use crate::{
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use global_lib::{messages::Algo, Wrapped};
+use std::io::Write;
+
+/// One beacon epoch: every node shares a fresh secret with `algo` in a session of its own, and the
+/// nodes agree through an ACS on the sessions whose sharing is over. The t + 1 first agreed secrets
+/// are then reconstructed, the beacon value being their sum. One of them comes from an honest node
+/// and stays hidden until the sessions are agreed on, so no dealer can predict or bias the value.
+pub async fn beacon(node: Wrapped<Node>, algo: Algo) {
+    let mut receiver = node
+        .lock()
+        .await
+        .subscribe(NodeMessage::BeaconSharingOverConst);
+    Node::share(node.clone(), algo).await;
+    match receiver.recv().await {
+        Some(NodeMessage::BeaconSharingOver) => {}
+        _ => return,
+    }
+    let agreed = match Node::agreed_sessions(&node).await {
+        Some(agreed) => agreed,
+        None => return,
+    };
+    let (t, session) = {
+        let node = node.lock().await;
+        (node.t() as usize, node.session() as u16)
+    };
+    if !agreed.iter().take(t + 1).any(|j| *j == session) {
+        Node::output(node);
+        return;
+    }
+    log!(node, "Beacon: the sharing is agreed on, reconstructing");
+    Node::reconstruct(node, algo).await;
+}
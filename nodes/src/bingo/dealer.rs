@@ -19,7 +19,10 @@ pub async fn deal(node: Wrapped<Node>) {
     let t = node.t() as usize;
     let (secrets, bi, mut comm) = {
         let rng = &mut thread_rng();
-        let secrets = random_scalars(node.config().batch_size() as usize, rng);
+        let secrets = match node.config().given_secrets() {
+            Some(secrets) => secrets.to_vec(),
+            None => random_scalars(node.config().batch_size() as usize, rng),
+        };
         let bi = BiVariatePoly::random_with_rands(secrets.clone(), 2 * t, t, node.config(), rng);
         (
             secrets,
@@ -32,7 +35,6 @@ pub async fn deal(node: Wrapped<Node>) {
     }
     let mut lines = Vec::with_capacity(n);
     let dom = Arc::new(node.dom().clone());
-    let corruption = node.config().dealer_corruption() as usize;
     let setup = comm.kzg_setup();
     let mut datas = stream::iter(1..=n)
         .map(|i| {
@@ -59,7 +61,10 @@ pub async fn deal(node: Wrapped<Node>) {
     let bytes = enc!(comm);
     node.reliable_broadcast(BroadcastMessageType::Bingo, bytes)
         .await;
-    for (i, ax) in (0..n - corruption).zip(lines) {
+    for (i, ax) in lines.into_iter().enumerate() {
+        if node.config().omits(i as u16) {
+            continue;
+        }
         let msg = enc!(Bingo, BingoCommand::MyLine, ax);
         node.contact(i, Arc::new(msg));
     }
@@ -7,15 +7,15 @@ use crate::{
     crypto::{Polynomial, Share},
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::BingoCommand, select, Step, Wrapped};
+use global_lib::{messages::BingoCommand, select, try_dec, Wrapped};
 
 pub async fn listen_at(node: Wrapped<Node>) {
     let mut receiver = node.lock().await.subscribe(NodeMessage::BingoSenderConst);
-    if node.lock().await.step() == Step::Sharing {
+    if node.lock().await.step().is_sharing() {
         let channel = NodeMessage::BroadcastBingoConst;
         let mut comm_receiver = node.lock().await.subscribe(channel);
         let comm = match comm_receiver.recv().await.unwrap() {
-            NodeMessage::BroadcastBingo(bytes) => dec!(bytes),
+            NodeMessage::BroadcastBingo(bytes) => try_dec!(bytes, return Node::abort(node)),
             _ => panic!("Unexpected message"),
         };
         node.lock().await.set_comm(comm);
@@ -56,12 +56,12 @@ pub async fn bingo_share(node: Wrapped<Node>) {
 }
 
 async fn my_line(node: Wrapped<Node>, bytes: &[u8]) {
-    let line: Polynomial = dec!(bytes);
+    let line: Polynomial = try_dec!(bytes);
     verify_my_line(node, line).await;
 }
 
 async fn new_row(node: Wrapped<Node>, bytes: &[u8]) {
-    let share: Share = dec!(bytes, Share);
+    let share: Share = try_dec!(bytes);
     let _ = node
         .lock()
         .await
@@ -70,7 +70,7 @@ async fn new_row(node: Wrapped<Node>, bytes: &[u8]) {
 }
 
 async fn new_col(node: Wrapped<Node>, bytes: &[u8]) {
-    let share: Share = dec!(bytes);
+    let share: Share = try_dec!(bytes);
     let _ = node
         .lock()
         .await
@@ -87,6 +87,6 @@ async fn new_done(node: Wrapped<Node>, _bytes: &[u8]) {
 }
 
 async fn new_reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
-    let msg = NodeMessage::BingoReconstructShare(dec!(bytes, Share));
+    let msg = NodeMessage::BingoReconstructShare(try_dec!(bytes));
     let _ = node.lock().await.try_send_message(msg).await;
 }
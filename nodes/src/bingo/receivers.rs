@@ -2,7 +2,7 @@ use crate::{
     break_if_over,
     crypto::{
         crypto_lib::{crypto_blstrs::poly_commit::kzg::BlstrsKZG, fft::fft},
-        kzg_interpolate_all_without_proofs,
+        kzg_complete_line,
         scheme::kzg_interpolate_all,
         Polynomial, Share,
     },
@@ -39,6 +39,7 @@ pub async fn verify_my_line(node: Wrapped<Node>, line: Polynomial) {
     }
     let shares = wrap!(vec!(None; n));
 
+    #[allow(clippy::too_many_arguments)]
     async fn handle_share_batch(
         begin: usize,
         end: usize,
@@ -54,7 +55,7 @@ pub async fn verify_my_line(node: Wrapped<Node>, line: Polynomial) {
         assert!(evaluations.len() == end - begin);
         assert!(end <= n);
         for (eval, mut i) in evaluations.into_iter().zip(begin..end) {
-            if index % 2 == 0 {
+            if index.is_multiple_of(2) {
                 i = n - i - 1
             }
             let r = node.lock().await.dom().get_root_of_unity(i);
@@ -176,7 +177,7 @@ pub async fn line_manager(node: Wrapped<Node>) {
                 if shares.len() == enough {
                     let node = node.lock().await;
                     shares.sort();
-                    let shares = kzg_interpolate_all_without_proofs(node.config(), &shares);
+                    let shares = kzg_complete_line(node.config(), &shares);
                     node.send_message(NodeMessage::BingoBroadcastDoneRequest(shares))
                         .await;
                 }
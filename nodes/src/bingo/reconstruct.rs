@@ -1,12 +1,17 @@
+use blstrs::G1Projective;
 use global_lib::{enc, messages::BingoCommand, wrap, Wrapped};
 use notifier_hub::notifier::MessageReceiver as Receiver;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Write};
 
 use crate::{
     break_if_over,
-    crypto::{interpolate_on_zero, kzg_interpolate_specific_share, Share},
-    node::{node::Node, node_message::NodeMessage},
+    crypto::{
+        crypto_lib::lagrange::lagrange_coefficients_at_zero,
+        kzg_interpolate_specific_share_with_proof, Commitment, Share,
+    },
+    log,
+    node::{configuration::Configuration, node::Node, node_message::NodeMessage},
 };
 
 const SECRET_TO_RECONSTRUCT: usize = 0;
@@ -20,7 +25,8 @@ pub async fn reconstruct(node: Wrapped<Node>) {
         return;
     }
     let share_index = node.config().index_secret(SECRET_TO_RECONSTRUCT);
-    let mut share = kzg_interpolate_specific_share(node.dom(), &node.shares_vec(), share_index);
+    let mut share =
+        kzg_interpolate_specific_share_with_proof(node.dom(), &node.shares_vec(), share_index);
     share.set_index(node.index());
     let msg = enc!(Bingo, BingoCommand::ReconstructShare, share);
     tokio::spawn(async move { reconstruct_share_receiver(cloned_node, receiver, share).await });
@@ -28,7 +34,6 @@ pub async fn reconstruct(node: Wrapped<Node>) {
     node.log("End of broadcast");
 }
 
-#[allow(clippy::needless_if)]
 async fn reconstruct_share_receiver(
     node: Wrapped<Node>,
     mut receiver: Receiver<NodeMessage>,
@@ -55,26 +60,29 @@ async fn reconstruct_share_receiver(
             match msg {
                 NodeMessage::BingoReconstructShare(share) => {
                     let i = share.index();
-                    assert!(
-                        !set.lock().await.contains_key(&i),
-                        "I got the same share two times: {i}"
-                    );
-                    assert!(
-                        comm.verify_on(i as usize, share_index, &share.get(0)),
-                        "I failed to verify a share: {i}"
-                    );
+                    let r = node.lock().await.dom().get_root_of_unity(share_index);
+                    let valid = share.uindex() < comm.all()[0].len()
+                        && comm.kzg_verify(share.uindex(), &r, share.first_share(), share.proof());
                     let mut set = set.lock().await;
+                    if !valid || set.contains_key(&i) || set.len() as u16 == enough {
+                        if !valid {
+                            log!(node, "Dropped the invalid share of {i}");
+                        }
+                        return;
+                    }
                     set.insert(i, share);
                     node.lock().await.log(&format!("New share: {}", set.len()));
                     if set.len() as u16 == enough {
-                        {
+                        let interpolated = {
                             let node = node.lock().await;
-                            let secret = interpolate_on_zero(node.config(), &set);
-                            let secrets = node.get_secrets();
-                            if secrets.is_some() && !secrets.as_ref().unwrap().contains(&secret) {}
-                            // TODO: Handle the interpolation fail
+                            verify_secret(node.config(), &comm, &set, share_index)
+                        };
+                        if interpolated {
+                            Node::output(node)
+                        } else {
+                            log!(node, "Failed to interpolate the secrets");
+                            Node::abort(node)
                         }
-                        Node::output(node);
                     }
                 }
                 _ => {
@@ -84,3 +92,36 @@ async fn reconstruct_share_receiver(
         });
     }
 }
+
+/// Interpolates the secret and its proof from the shares, and checks them against the commitment
+/// interpolated from the commitments of the lines of the nodes. The dealt polynomial doesn't embed
+/// the secrets of the dealer, so they are not compared.
+fn verify_secret(
+    sc: &Configuration,
+    comm: &Commitment,
+    shares: &HashMap<u16, Share>,
+    share_index: usize,
+) -> bool {
+    let dom = sc.get_batch_evaluation_domain();
+    let selected = shares.keys().map(|i| *i as usize).collect::<Vec<_>>();
+    // The line of the node u is the evaluation of the bivariate polynomial at the index u + 1
+    let lagr =
+        lagrange_coefficients_at_zero(dom, &selected.iter().map(|i| i + 1).collect::<Vec<_>>());
+    let lines = &comm.all()[0];
+    let points = selected.iter().map(|i| lines[*i]).collect::<Vec<_>>();
+    let proofs = selected
+        .iter()
+        .map(|i| *shares[&(*i as u16)].proof())
+        .collect::<Vec<_>>();
+    let secret = selected
+        .iter()
+        .zip(&lagr)
+        .map(|(i, l)| shares[&(*i as u16)].first_share() * l)
+        .sum();
+    comm.kzg_setup().verify(
+        &G1Projective::multi_exp(&points, &lagr),
+        &dom.get_root_of_unity(share_index),
+        &secret,
+        &G1Projective::multi_exp(&proofs, &lagr),
+    )
+}
@@ -1,11 +1,13 @@
 use super::broadcast_message_types::{BroadcastMessageType, Transcript};
 use crate::{
-    crypto::data_structures::reed_solomon_code::{reed_solomon_encode, RSDecoder, RSDecoderData},
+    crypto::data_structures::reed_solomon_code::{
+        reed_solomon_datas, reed_solomon_encode, RSDecoder, RSDecoderData,
+    },
     node::node::Node,
 };
 use global_lib::{enc, messages::BroadcastCommand, Wrapped};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 type Bytes = Vec<u8>;
@@ -13,79 +15,159 @@ type Bytes = Vec<u8>;
 #[derive(PartialEq)]
 enum ReadyState {
     None,
-    Wait,
     Ready,
 }
 
-struct MessageManager {
-    hash: Bytes,
+/// No broadcast of the testbed gets near this size, it bounds the decoding of a forged length
+const MAX_MESSAGE_LEN: usize = 1 << 30;
+
+/// Echoes and readies received for one of the messages proposed in an instance
+struct Votes {
+    datas: RSDecoderData,
     my_share: Bytes,
-    decoder: RSDecoder,
-    echo: u8,
-    ready: u8,
+    fragments: Vec<(usize, Bytes)>, // Shares of the readies, in the order they arrived
+    decoded: usize,                 // Number of fragments of the last decoding
+    echoes: HashSet<u16>,
+    readies: HashSet<u16>,
+}
+
+impl Votes {
+    fn new(datas: RSDecoderData) -> Self {
+        Self {
+            datas,
+            my_share: Vec::new(),
+            fragments: Vec::new(),
+            decoded: 0,
+            echoes: HashSet::new(),
+            readies: HashSet::new(),
+        }
+    }
+
+    fn datas(&self) -> RSDecoderData {
+        self.datas
+    }
+
+    fn have_my_share(&self) -> bool {
+        !self.my_share.is_empty()
+    }
+
+    /// Decodes the message matching the hash, a Byzantine node may have sent a wrong fragment. The
+    /// first time every subset of t + 1 fragments is tried, then only the ones with the last one.
+    fn decode(&mut self, hash: &Bytes) -> Option<Bytes> {
+        let k = self.datas.t;
+        if self.fragments.len() < k || self.fragments.len() == self.decoded {
+            return None;
+        }
+        let message = match self.decoded {
+            0 => find_message(self.datas, hash, &[], &self.fragments, k),
+            _ => {
+                let (last, others) = self.fragments.split_last().unwrap();
+                find_message(self.datas, hash, std::slice::from_ref(last), others, k - 1)
+            }
+        };
+        self.decoded = self.fragments.len();
+        message
+    }
+}
+
+/// Decodes the fixed fragments with every subset of k of the others, until the message matches
+fn find_message(
+    datas: RSDecoderData,
+    hash: &Bytes,
+    fixed: &[(usize, Bytes)],
+    others: &[(usize, Bytes)],
+    k: usize,
+) -> Option<Bytes> {
+    let mut subset = (0..k).collect::<Vec<_>>();
+    loop {
+        let mut decoder = RSDecoder::new(datas);
+        for (i, share) in fixed.iter().chain(subset.iter().map(|j| &others[*j])) {
+            decoder.add_recovery_share(*i, share);
+        }
+        let message = decoder.decode();
+        if MessageManager::get_message_hash(&message) == *hash {
+            return Some(message);
+        }
+        let mut p = k;
+        loop {
+            if p == 0 {
+                return None;
+            }
+            p -= 1;
+            if subset[p] < others.len() - k + p {
+                break;
+            }
+        }
+        subset[p] += 1;
+        for q in p + 1..k {
+            subset[q] = subset[q - 1] + 1;
+        }
+    }
+}
+
+type VotesId = (Bytes, usize); // (hash of the message, length of the message)
+
+struct MessageManager {
+    // The votes by message, an equivocating origin may get several of them echoed
+    votes: HashMap<VotesId, Votes>,
+    // Only the first proposal of the origin is echoed
+    echoed: bool,
     ready_state: ReadyState,
     node: Wrapped<Node>,
     kind: BroadcastMessageType,
+    origin: u16,
+    index: u16,
+    n: usize,
+    t: usize,
 }
 
+type InstanceId = (BroadcastMessageType, u16);
+
 impl MessageManager {
-    async fn new(node: Wrapped<Node>, kind: BroadcastMessageType, decoder: RSDecoder) -> Self {
+    async fn new(node: Wrapped<Node>, (kind, origin): InstanceId) -> Self {
+        let (index, n) = {
+            let node = node.lock().await;
+            (node.index(), node.n() as usize)
+        };
         Self {
             node,
             kind,
-            decoder,
+            origin,
+            index,
+            n,
+            t: n / 3,
             ready_state: ReadyState::None,
-            my_share: Vec::new(),
-            echo: 0,
-            ready: 0,
-            hash: Vec::new(),
+            echoed: false,
+            votes: HashMap::new(),
         }
     }
 
-    async fn encode_message(
-        node: &Wrapped<Node>,
-        message: Bytes,
-    ) -> (Vec<Bytes>, RSDecoderData, Bytes) {
-        let (n, t) = {
-            let node = node.lock().await;
-            (node.n() as usize, node.n() as usize / 3)
-        };
-        let hash = Self::get_message_hash(&message);
-        let (shares, datas) = reed_solomon_encode(message, n, t + 1);
-        (shares, datas, hash)
-    }
-
-    async fn new_from_message(
-        node: Wrapped<Node>,
-        kind: BroadcastMessageType,
-        message: Bytes,
-    ) -> Self {
-        let (shares, datas, hash) = Self::encode_message(&node, message).await;
-        let mut mem = Self::new(node, kind, RSDecoder::new(datas)).await;
-        mem.set_message(kind, shares, datas, hash).await;
+    async fn new_from_message(node: Wrapped<Node>, id: InstanceId, message: Bytes) -> Self {
+        let mut mem = Self::new(node, id).await;
+        mem.set_message(message).await;
         mem
     }
 
-    async fn set_message(
-        &mut self,
-        kind: BroadcastMessageType,
-        shares: Vec<Bytes>,
-        datas: RSDecoderData,
-        hash: Bytes,
-    ) {
-        self.hash = hash.clone();
+    async fn set_message(&mut self, message: Bytes) {
+        if self.echoed {
+            return;
+        }
+        self.echoed = true;
+        let hash = Self::get_message_hash(&message);
+        let (shares, datas) = reed_solomon_encode(message, self.n, self.t + 1);
         let mut tr = Transcript {
-            kind,
+            kind: self.kind,
+            origin: self.origin,
             datas,
             hash,
-            i: self.node.lock().await.index(),
+            i: self.index,
             share: Vec::new(),
         };
         let mut my_share = Vec::new();
         {
             let mut node = self.node.lock().await;
             for (i, share) in shares.into_iter().enumerate() {
-                if i == node.uindex() {
+                if i == self.index as usize {
                     my_share = share;
                     continue;
                 }
@@ -93,49 +175,53 @@ impl MessageManager {
                 let msg = Arc::new(enc!(Broadcast, BroadcastCommand::Echo, tr));
                 node.contact(i, msg);
             }
-            node.index()
-        };
+        }
         tr.share = my_share;
         self.add_echo(tr).await;
     }
 
     async fn new_from_echo(node: Wrapped<Node>, tr: Transcript) -> Self {
-        let mut res = Self::new(node, tr.kind, RSDecoder::new(tr.datas)).await;
+        let mut res = Self::new(node, (tr.kind, tr.origin)).await;
         res.add_echo(tr).await;
         res
     }
 
     async fn new_from_ready(node: Wrapped<Node>, tr: Transcript) -> Self {
-        let mut res = Self::new(node, tr.kind, RSDecoder::new(tr.datas)).await;
+        let mut res = Self::new(node, (tr.kind, tr.origin)).await;
         res.add_ready(tr).await;
         res
     }
 
-    async fn broadcast_ready(&mut self) {
-        if !self.have_my_share() {
-            self.ready_state = ReadyState::None;
-        }
-        let node_i = self.node.lock().await.index();
-        if self.ready_state != ReadyState::Ready {
-            let tr = Transcript {
-                hash: self.hash.clone(),
-                i: node_i,
-                datas: self.datas(),
-                share: self.my_share.clone(),
-                kind: self.kind,
-            };
-            let msg = enc!(Broadcast, BroadcastCommand::Ready, tr);
-            self.node.lock().await.broadcast(msg, false).await;
-            self.ready_state = ReadyState::Ready;
+    /// The votes of the message of the transcript, whose encoding parameters must be the ones of
+    /// the network for its length
+    fn votes(&mut self, tr: &Transcript) -> Option<&mut Votes> {
+        let len = tr.datas.original_message_len;
+        if len > MAX_MESSAGE_LEN || tr.datas != reed_solomon_datas(len, self.n, self.t + 1) {
+            return None;
         }
+        let votes = self
+            .votes
+            .entry((tr.hash.clone(), len))
+            .or_insert_with(|| Votes::new(tr.datas));
+        Some(votes)
     }
 
-    fn datas(&self) -> RSDecoderData {
-        self.decoder.datas()
-    }
-
-    fn have_my_share(&self) -> bool {
-        !self.my_share.is_empty()
+    async fn broadcast_ready(&mut self, id: &VotesId) {
+        if self.ready_state == ReadyState::Ready {
+            return;
+        }
+        let votes = &self.votes[id];
+        let tr = Transcript {
+            i: self.index,
+            datas: votes.datas(),
+            share: votes.my_share.clone(),
+            hash: id.0.clone(),
+            kind: self.kind,
+            origin: self.origin,
+        };
+        let msg = enc!(Broadcast, BroadcastCommand::Ready, tr);
+        self.node.lock().await.broadcast(msg, false).await;
+        self.ready_state = ReadyState::Ready;
     }
 
     fn get_message_hash(message: &Bytes) -> Bytes {
@@ -144,62 +230,62 @@ impl MessageManager {
         hasher.finalize().to_vec()
     }
 
-    fn check_hash(&self, hash: Bytes) -> Result<(), ()> {
-        if self.hash == hash {
-            Ok(())
-        } else {
-            Err(())
-        }
-    }
-
-    fn set_hash(&mut self, hash: Bytes) -> Result<(), ()> {
-        if self.hash.is_empty() {
-            self.hash = hash;
-            Ok(())
-        } else {
-            self.check_hash(hash)
-        }
-    }
-
     async fn add_echo(&mut self, mut tr: Transcript) {
-        self.set_hash(tr.hash.clone()).unwrap();
-        self.echo += 1;
-        // NOTE: datas.t reprensents the number of share needed to reconstruct (t+1), as we want 2*t + 1 echo to broadcast ready we will need datas.t * 2 - 1 echo that is equal at 2*t+1.
-        if self.echo as usize == 2 * self.datas().t - 1 {
-            self.broadcast_ready().await
-        } else if !self.have_my_share() {
-            tr.i = self.node.lock().await.index();
-            self.my_share = tr.share.clone();
+        let (index, origin, t) = (self.index, self.origin, self.t);
+        let votes = match self.votes(&tr) {
+            Some(votes) => votes,
+            None => return,
+        };
+        if !votes.echoes.insert(tr.i) {
+            return;
+        }
+        let id = (tr.hash.clone(), tr.datas.original_message_len);
+        if votes.echoes.len() == 2 * t + 1 {
+            self.broadcast_ready(&id).await
+        } else if !votes.have_my_share() && (tr.i == index || tr.i == origin) {
+            // The share of the node is the one it encoded or the one the origin sent it
+            votes.my_share = tr.share.clone();
+            tr.i = index;
             self.add_ready(tr).await;
-            if self.ready_state == ReadyState::Wait {
-                self.broadcast_ready().await;
-            }
         }
     }
 
     async fn add_ready(&mut self, tr: Transcript) -> bool {
+        let (n, t) = (self.n, self.t);
+        let votes = match self.votes(&tr) {
+            Some(votes) => votes,
+            None => return false,
+        };
+        let id = (tr.hash.clone(), tr.datas.original_message_len);
         let Transcript { hash, share, i, .. } = tr;
+        // A share of the wrong size can't come from the encoding of the message
+        if !share.is_empty() && (share.len() != votes.datas().pow_2_size || i as usize >= n) {
+            return false;
+        }
+        if !votes.readies.insert(i) {
+            return false;
+        }
         if !share.is_empty() {
-            self.set_hash(hash).unwrap();
-            assert!(share.len() == self.datas().pow_2_size);
-            self.decoder.add_recovery_share(i as usize, &share);
+            votes.fragments.push((i as usize, share));
         }
-        self.ready += 1;
-        let t = self.datas().t - 1;
-        let output = self.ready as usize == t * 2 + 1;
-        if self.ready as usize == t + 1 {
-            self.broadcast_ready().await;
-        } else if output {
-            self.output().await
+        let ready = votes.readies.len();
+        if ready == t + 1 {
+            self.broadcast_ready(&id).await;
+        }
+        if ready < 2 * t + 1 {
+            return false;
+        }
+        match self.votes.get_mut(&id).unwrap().decode(&hash) {
+            Some(message) => {
+                self.output(message);
+                true
+            }
+            None => false,
         }
-        output
     }
 
-    async fn output(&mut self) {
-        let message = self.decoder.decode();
-        let hash = Self::get_message_hash(&message);
-        self.check_hash(hash).unwrap();
-        let message = self.kind.get_node_message(message);
+    fn output(&mut self, message: Bytes) {
+        let message = self.kind.get_node_message(self.origin, message);
         let node = self.node.clone();
         tokio::spawn(async move {
             Node::wait_and_send(&node, message).await;
@@ -209,7 +295,7 @@ impl MessageManager {
 
 pub struct BroadcastMemory {
     node: Wrapped<Node>,
-    proposed: HashMap<BroadcastMessageType, Option<MessageManager>>,
+    proposed: HashMap<InstanceId, Option<MessageManager>>,
 }
 
 impl BroadcastMemory {
@@ -220,20 +306,25 @@ impl BroadcastMemory {
         }
     }
 
-    pub async fn propose(&mut self, kind: BroadcastMessageType, bytes: Bytes) {
-        match self.proposed.get_mut(&kind) {
+    /// Only the dealer broadcasts the commitment of a sharing, the instances of the other nodes are
+    /// ignored
+    async fn expected(&self, (kind, origin): InstanceId) -> bool {
+        !kind.from_dealer() || origin == self.node.lock().await.config().dealer()
+    }
+
+    pub async fn propose(&mut self, kind: BroadcastMessageType, origin: u16, bytes: Bytes) {
+        let id = (kind, origin);
+        if !self.expected(id).await {
+            return;
+        }
+        match self.proposed.get_mut(&id) {
             Some(manager) if manager.is_some() => {
-                let (shares, datas, hash) = MessageManager::encode_message(&self.node, bytes).await;
-                manager
-                    .as_mut()
-                    .unwrap()
-                    .set_message(kind, shares, datas, hash)
-                    .await
+                manager.as_mut().unwrap().set_message(bytes).await
             }
             None => {
                 let _ = self.proposed.insert(
-                    kind,
-                    Some(MessageManager::new_from_message(self.node.clone(), kind, bytes).await),
+                    id,
+                    Some(MessageManager::new_from_message(self.node.clone(), id, bytes).await),
                 );
             }
             _ => (),
@@ -241,29 +332,35 @@ impl BroadcastMemory {
     }
 
     pub async fn add_ready(&mut self, tr: Transcript) {
-        let kind = tr.kind;
-        if match self.proposed.get_mut(&kind) {
+        let id = (tr.kind, tr.origin);
+        if !self.expected(id).await {
+            return;
+        }
+        if match self.proposed.get_mut(&id) {
             Some(manager) if manager.is_some() => manager.as_mut().unwrap().add_ready(tr).await,
             None => {
                 let _ = self.proposed.insert(
-                    kind,
+                    id,
                     Some(MessageManager::new_from_ready(self.node.clone(), tr).await),
                 );
                 false
             }
             _ => false,
         } {
-            self.proposed.insert(kind, None);
+            self.proposed.insert(id, None);
         }
     }
 
     pub async fn add_echo(&mut self, tr: Transcript) {
-        let kind = tr.kind;
-        match self.proposed.get_mut(&kind) {
+        let id = (tr.kind, tr.origin);
+        if !self.expected(id).await {
+            return;
+        }
+        match self.proposed.get_mut(&id) {
             Some(manager) if manager.is_some() => manager.as_mut().unwrap().add_echo(tr).await,
             None => {
                 let _ = self.proposed.insert(
-                    kind,
+                    id,
                     Some(MessageManager::new_from_echo(self.node.clone(), tr).await),
                 );
             }
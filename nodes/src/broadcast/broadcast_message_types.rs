@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize)]
 pub struct Transcript {
     pub i: u16,
+    pub origin: u16,
     pub kind: BroadcastMessageType,
     pub hash: Vec<u8>,
     pub datas: RSDecoderData,
@@ -23,18 +24,43 @@ as_number!(
         Badger,
         LightWeight,
         HbAvss,
+        Feldman,
+        NiPvss,
+        Acs,
+        Reshare,
+        Triples,
     },
     derive(Hash, Copy, Eq, PartialEq, Clone, Serialize, Deserialize)
 );
 
 impl BroadcastMessageType {
-    pub fn get_node_message(self, message: Vec<u8>) -> NodeMessage {
+    /// Whether the message is the commitment of the dealer of a sharing, any node broadcasts the
+    /// messages of the other kinds
+    pub fn from_dealer(self) -> bool {
+        matches!(
+            self,
+            Self::AvssSimpl
+                | Self::Bingo
+                | Self::Badger
+                | Self::LightWeight
+                | Self::HbAvss
+                | Self::Feldman
+                | Self::NiPvss
+        )
+    }
+
+    pub fn get_node_message(self, origin: u16, message: Vec<u8>) -> NodeMessage {
         match self {
             Self::AvssSimpl => NodeMessage::BroadcastAvssSimpl(message),
             Self::Bingo => NodeMessage::BroadcastBingo(message),
             Self::Badger => NodeMessage::BroadcastBadger(message),
             Self::LightWeight => NodeMessage::BroadcastLightWeight(message),
             Self::HbAvss => NodeMessage::BroadcastHbAvss(message),
+            Self::Feldman => NodeMessage::BroadcastFeldman(message),
+            Self::NiPvss => NodeMessage::BroadcastNiPvss(message),
+            Self::Acs => NodeMessage::BroadcastAcs(origin, message),
+            Self::Reshare => NodeMessage::BroadcastReshare(origin, message),
+            Self::Triples => NodeMessage::BroadcastTriples(origin, message),
         }
     }
 }
@@ -2,7 +2,7 @@ use crate::{
     break_if_over, log,
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::BroadcastCommand, select, wrap, Wrapped};
+use global_lib::{messages::BroadcastCommand, select, try_dec, wrap, Wrapped};
 use std::io::Write;
 
 use super::{
@@ -20,19 +20,22 @@ pub async fn listen(node: Wrapped<Node>) {
     loop {
         let msg = break_if_over!(receiver);
         match msg {
-            NodeMessage::BroadcastSender(mut bytes_message) => {
+            NodeMessage::BroadcastSender(sender, mut bytes_message) => {
                 let memory = memory.clone();
                 log!(
                     node,
                     "Broadcast: New Message, {:?}",
-                    BroadcastCommand::from(bytes_message[0])
+                    bytes_message
+                        .first()
+                        .copied()
+                        .and_then(BroadcastCommand::from_checked)
                 );
                 handlers.push(select!(
                     as_vec
                     BroadcastCommand, bytes_message, memory,
-                    Propose => propose,
-                    Echo => new_echo,
-                    Ready => new_ready
+                    Propose => propose sender,
+                    Echo => new_echo sender,
+                    Ready => new_ready sender
                 ));
             }
             _ => panic!("Unexpected message"),
@@ -43,17 +46,31 @@ pub async fn listen(node: Wrapped<Node>) {
     }
 }
 
-async fn propose(memory: Wrapped<BroadcastMemory>, mut bytes: Vec<u8>) {
-    let kind = BroadcastMessageType::from(bytes.remove(0));
-    memory.lock().await.propose(kind, bytes).await;
+/// The origin of a proposal and the voter of an echo or a ready are the sender of the message
+async fn propose(memory: Wrapped<BroadcastMemory>, mut bytes: Vec<u8>, origin: u16) {
+    let kind = match bytes
+        .first()
+        .copied()
+        .and_then(BroadcastMessageType::from_checked)
+    {
+        Some(kind) => kind,
+        None => return,
+    };
+    memory
+        .lock()
+        .await
+        .propose(kind, origin, bytes.split_off(1))
+        .await;
 }
 
-async fn new_echo(memory: Wrapped<BroadcastMemory>, bytes: Vec<u8>) {
-    let tr: Transcript = dec!(bytes);
+async fn new_echo(memory: Wrapped<BroadcastMemory>, bytes: Vec<u8>, sender: u16) {
+    let mut tr: Transcript = try_dec!(bytes);
+    tr.i = sender;
     memory.lock().await.add_echo(tr).await;
 }
 
-async fn new_ready(memory: Wrapped<BroadcastMemory>, bytes: Vec<u8>) {
-    let tr: Transcript = dec!(bytes);
+async fn new_ready(memory: Wrapped<BroadcastMemory>, bytes: Vec<u8>, sender: u16) {
+    let mut tr: Transcript = try_dec!(bytes);
+    tr.i = sender;
     memory.lock().await.add_ready(tr).await;
 }
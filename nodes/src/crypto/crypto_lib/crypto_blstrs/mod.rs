@@ -105,13 +105,10 @@ where
             mul_poly(&mut poly_j, &xs[i]);
             mul_poly(&mut polys[i], &xs[j]);
             let diff = inv(&xs[j] - &xs[i]);
-            polys[i]
-                .iter_mut()
-                .zip(poly_j.into_iter())
-                .for_each(|(x, y)| {
-                    *x -= y;
-                    *x *= &diff;
-                });
+            polys[i].iter_mut().zip(poly_j).for_each(|(x, y)| {
+                *x -= y;
+                *x *= &diff;
+            });
         }
     }
     if polys.is_empty() {
@@ -1,6 +1,7 @@
 use crate::crypto::crypto_lib::crypto_blstrs::polynomial::BlstrsPolynomial;
 use anyhow::{ensure, Result};
 use blstrs::{G1Projective, Scalar};
+use ff::Field;
 use group::Group;
 use serde::{Deserialize, Serialize};
 use std::ops::Mul;
@@ -27,7 +28,7 @@ type Commitment = Vec<G1Projective>;
 type Witness = ();
 
 impl BlstrsFeldman {
-    fn commit(&self, poly: &BlstrsPolynomial) -> Result<Vec<G1Projective>> {
+    pub fn commit(&self, poly: &BlstrsPolynomial) -> Result<Vec<G1Projective>> {
         ensure!(
             poly.degree() <= self.max_degree,
             "Polynomial degree is too large!"
@@ -39,7 +40,7 @@ impl BlstrsFeldman {
         Ok(commitment)
     }
 
-    fn open(&self, poly: &BlstrsPolynomial, x: &Scalar) -> Result<(Scalar, Witness)> {
+    pub fn open(&self, poly: &BlstrsPolynomial, x: &Scalar) -> Result<(Scalar, Witness)> {
         ensure!(
             poly.degree() <= self.max_degree,
             "Polynomial degree is too large!"
@@ -48,12 +49,16 @@ impl BlstrsFeldman {
         Ok((eval, ()))
     }
 
-    fn open_commit(&self, poly: &BlstrsPolynomial, x: &Scalar) -> Result<(G1Projective, Witness)> {
+    pub fn open_commit(
+        &self,
+        poly: &BlstrsPolynomial,
+        x: &Scalar,
+    ) -> Result<(G1Projective, Witness)> {
         let (y, witness) = self.open(poly, x)?;
         Ok((self.generator.mul(y), witness))
     }
 
-    fn verify(
+    pub fn verify(
         &self,
         commitment: &Commitment,
         x: &Scalar,
@@ -64,24 +69,27 @@ impl BlstrsFeldman {
     }
 
     #[allow(unused_variables)]
-    // TODO this isn't really used in the code but this is slow and should be changed to multi exp.
-    fn verify_from_commitment(
+    pub fn verify_from_commitment(
         &self,
         commitment: &Commitment,
         x: &Scalar,
         value: &G1Projective,
         witness: &Witness,
     ) -> bool {
-        if commitment.len() == 0 {
-            return value == &G1Projective::identity();
+        value == &self.evaluate_commitment(commitment, x)
+    }
+
+    pub fn evaluate_commitment(&self, commitment: &Commitment, x: &Scalar) -> G1Projective {
+        if commitment.is_empty() {
+            return G1Projective::identity();
         }
 
-        let mut x_pows = x.clone();
-        let mut sum = commitment[0].clone();
-        for i in 1..commitment.len() {
-            sum += commitment[i].mul(&x_pows);
-            x_pows *= x;
+        let mut x_pows = Vec::with_capacity(commitment.len());
+        let mut x_pow = Scalar::one();
+        for _ in 0..commitment.len() {
+            x_pows.push(x_pow);
+            x_pow *= x;
         }
-        value == &sum
+        G1Projective::multi_exp(commitment, &x_pows)
     }
 }
@@ -1,2 +1,2 @@
-// pub mod feldman;
+pub mod feldman;
 pub mod kzg;
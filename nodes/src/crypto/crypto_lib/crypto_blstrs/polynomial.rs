@@ -134,7 +134,7 @@ impl BlstrsPolynomial {
         Ok(Self::new(q))
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Scalar> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Scalar> {
         self.coeffs.iter()
     }
 }
@@ -203,10 +203,10 @@ impl BlstrsPolynomial {
         let max_degree = usize::max(self.coeffs.len(), other.coeffs.len());
         let mut result_coeffs = vec![Scalar::zero(); max_degree];
 
-        for i in 0..max_degree {
+        for (i, coeff) in result_coeffs.iter_mut().enumerate() {
             let a_coeff = self.coeffs.get(i).cloned().unwrap_or(Scalar::zero());
             let b_coeff = other.coeffs.get(i).cloned().unwrap_or(Scalar::zero());
-            result_coeffs[i] = a_coeff - b_coeff;
+            *coeff = a_coeff - b_coeff;
         }
 
         BlstrsPolynomial {
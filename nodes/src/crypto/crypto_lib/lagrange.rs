@@ -268,7 +268,7 @@ pub fn all_lagrange_denominators(batch_dom: &BatchEvaluationDomain, n: usize) ->
     poly_differentiate(&mut A);
 
     // A'(\omega^i) = \prod_{j\ne i, j \in [n] } (\omega^i - \omega^j)
-    let mut denoms = fft(&A, &batch_dom.get_subdomain(n));
+    let mut denoms = fft(&A, &batch_dom.get_subdomain(batch_dom.N()));
 
     denoms.truncate(n);
 
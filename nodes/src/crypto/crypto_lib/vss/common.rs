@@ -94,8 +94,8 @@ pub fn get_dual_code_word<R: rand_core::RngCore + rand_core::CryptoRng>(
     // The "dual" polynomial f(X) of degree n - t - 1 = n - (deg + 1) - 1 = n - deg - 2
     let mut f = random_scalars(n - deg - 2, &mut rng);
 
-    // Compute f(\omega^i) for all i's
-    let dom = batch_dom.get_subdomain(n);
+    // Compute f(\omega^i) for all i's, the domain being larger than n when secrets are packed
+    let dom = batch_dom.get_subdomain(batch_dom.N());
     fft_assign(&mut f, &dom);
     f.truncate(n);
 
@@ -113,7 +113,7 @@ pub fn get_dual_code_word<R: rand_core::RngCore + rand_core::CryptoRng>(
 }
 
 pub fn sign_verified_deal(sig_key: Ed25519PrivateKey, msg: Vec<u8>) -> Option<Ed25519Signature> {
-    return Some(sig_key.sign_arbitrary_message(msg.as_slice()));
+    Some(sig_key.sign_arbitrary_message(msg.as_slice()))
 }
 
 // Helper function to generate N bls12381 private keys.
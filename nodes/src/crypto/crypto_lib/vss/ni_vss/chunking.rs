@@ -25,7 +25,7 @@ pub(crate) const MESSAGE_BYTES: usize = SCALAR_NUM_BYTES;
 
 /// NUM_CHUNKS is simply the number of chunks needed to hold a message
 // pub const NUM_CHUNKS: usize = 24;
-pub const NUM_CHUNKS: usize = (MESSAGE_BYTES + CHUNK_BYTES - 1) / CHUNK_BYTES;
+pub const NUM_CHUNKS: usize = MESSAGE_BYTES.div_ceil(CHUNK_BYTES);
 
 #[derive(Clone, Debug, Zeroize)]
 pub struct PlaintextChunks {
@@ -28,10 +28,10 @@ pub const SECURITY_LEVEL: usize = 256;
 pub const NUM_ZK_REPETITIONS: usize = 32;
 
 /// Defined as ceil(SECURITY_LEVEL/NUM_ZK_REPETITIONS)
-pub const CHALLENGE_BITS: usize = (SECURITY_LEVEL + NUM_ZK_REPETITIONS - 1) / NUM_ZK_REPETITIONS;
+pub const CHALLENGE_BITS: usize = SECURITY_LEVEL.div_ceil(NUM_ZK_REPETITIONS);
 
 // The number of bytes needed to represent a challenge (which must fit in a usize)
-pub const CHALLENGE_BYTES: usize = (CHALLENGE_BITS + 7) / 8;
+pub const CHALLENGE_BYTES: usize = CHALLENGE_BITS.div_ceil(8);
 const _: () = assert!(CHALLENGE_BYTES < std::mem::size_of::<usize>());
 
 // A bitmask specifyng the size of a challenge
@@ -221,9 +221,8 @@ pub fn prove_chunking(
     let xpowers = get_xpowers(&second_challenge, NUM_ZK_REPETITIONS);
 
     let mut z_r = Vec::with_capacity(first_challenge.len());
-    let mut delta_idx = 1;
 
-    for e_i in first_challenge.iter() {
+    for (delta_idx, e_i) in (1..).zip(first_challenge.iter()) {
         let mut xpow_e_ij = Vec::with_capacity(e_i.len());
         for j in 0..e_i.len() {
             xpow_e_ij.push(scalar_usize_mult_exp(&xpowers, &e_i[j]));
@@ -232,8 +231,6 @@ pub fn prove_chunking(
         let z_rk = scalar_mult_exp(&witness.scalars_r, &xpow_e_ij) + delta[delta_idx];
 
         z_r.push(z_rk);
-
-        delta_idx += 1;
     }
 
     let z_beta = scalar_mult_exp(&beta, &xpowers) + delta[0];
@@ -1,15 +1,17 @@
 use global_lib::messages::Algo;
 
-use crate::crypto::{Commitment, Secret, Share};
-use std::collections::HashMap;
+use crate::crypto::{Commitment, EncRandComm, RandComm, Secret, Share};
+use std::{collections::HashMap, ops::AddAssign};
 type Set = HashMap<u16, Share>;
-pub type CryptoSetIdentity = (u16, u16, Algo); // (n, t, algo)
+pub type CryptoSetIdentity = (u16, u16, Algo, u8); // (n, t, algo, session)
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct CryptoSet {
     identity: CryptoSetIdentity,
     comm: Option<Commitment>,
     secrets: Option<Vec<Secret>>,
+    rand_comms: Option<Vec<RandComm>>, // h^r of my share when r is unknown
+    enc_rand_comms: HashMap<u16, Vec<EncRandComm>>, // Encrypted h^r of the shares decrypted by their node
     set: Set,
 }
 
@@ -26,6 +28,8 @@ impl CryptoSet {
             identity: self.identity,
             comm: Some(self.get_comm().clone()),
             secrets: self.get_secrets().clone(),
+            rand_comms: self.get_rand_comms().clone(),
+            enc_rand_comms: self.enc_rand_comms.clone(),
             set: self.set().clone(),
         }
     }
@@ -34,6 +38,10 @@ impl CryptoSet {
         self.identity
     }
 
+    pub fn set_identity(&mut self, identity: CryptoSetIdentity) {
+        self.identity = identity;
+    }
+
     pub fn set(&self) -> &Set {
         &self.set
     }
@@ -99,4 +107,36 @@ impl CryptoSet {
     pub fn set_secrets(&mut self, secrets: Vec<Secret>) {
         self.secrets = Some(secrets);
     }
+
+    pub fn get_rand_comms(&self) -> &Option<Vec<RandComm>> {
+        &self.rand_comms
+    }
+
+    pub fn set_rand_comms(&mut self, rand_comms: Vec<RandComm>) {
+        self.rand_comms = Some(rand_comms);
+    }
+
+    pub fn get_enc_rand_comms(&self, i: u16) -> Option<&Vec<EncRandComm>> {
+        self.enc_rand_comms.get(&i)
+    }
+
+    pub fn set_enc_rand_comms(&mut self, i: u16, enc_rand_comms: Vec<EncRandComm>) {
+        self.enc_rand_comms.insert(i, enc_rand_comms);
+    }
+}
+
+/// Adds a sharing to the set, shares and commitments being homomorphic
+impl AddAssign<&CryptoSet> for CryptoSet {
+    fn add_assign(&mut self, other: &CryptoSet) {
+        for (i, share) in other.set() {
+            match self.set.get_mut(i) {
+                Some(mine) => *mine += share,
+                None => self.new_share(share.clone()),
+            }
+        }
+        match self.comm.as_mut() {
+            Some(comm) => *comm += other.get_comm(),
+            None => self.comm = other.comm.clone(),
+        }
+    }
 }
@@ -111,8 +111,7 @@ impl BiVariatePoly {
         let fields: Vec<Scalar> = self
             .fields
             .iter()
-            .enumerate()
-            .map(|(_, col)| {
+            .map(|col| {
                 col.fields()
                     .iter()
                     .zip(&powers)
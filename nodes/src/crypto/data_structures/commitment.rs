@@ -9,7 +9,7 @@ use ff::Field;
 use group::Group;
 use rand::thread_rng;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{ops::AddAssign, sync::Arc};
 
 #[derive(Serialize, Debug, Clone, Deserialize, Eq, PartialEq)]
 pub struct Commitment {
@@ -73,6 +73,17 @@ impl Commitment {
         comm
     }
 
+    pub fn push_line(&mut self, line: Vec<G1Projective>) {
+        self.comms.push(line);
+    }
+
+    /// Moves the first point of every line off its polynomial, failing the low degree test
+    pub fn corrupt(&mut self) {
+        for line in self.comms.iter_mut() {
+            line[0] += G1Projective::generator();
+        }
+    }
+
     pub fn add(&mut self, shares: &[Scalar], rand: &[Scalar]) {
         self.comms.push(self.compute_line(shares, rand));
     }
@@ -125,6 +136,38 @@ impl Commitment {
         self.get(b, i).eq(&e_com)
     }
 
+    pub fn verify_with_rand_comm_on(
+        &self,
+        b: usize,
+        i: usize,
+        share: &Scalar,
+        rand_comm: &G1Projective,
+    ) -> bool {
+        let e_com = self.base()[0] * share + rand_comm;
+        self.get(b, i).eq(&e_com)
+    }
+
+    pub fn verify_coeffs_on(&self, b: usize, x: &Scalar, share: &[Scalar; 2]) -> bool {
+        let mut powers = Vec::with_capacity(self.comms[b].len());
+        let mut power = Scalar::one();
+        for _ in 0..self.comms[b].len() {
+            powers.push(power);
+            power *= x;
+        }
+        let e_com = G1Projective::multi_exp(self.base.as_ref().unwrap(), share);
+        G1Projective::multi_exp(&self.comms[b], &powers).eq(&e_com)
+    }
+
+    pub fn verify_coeffs(&self, share: &Share, x: &Scalar) -> bool {
+        !share
+            .only_share()
+            .batch()
+            .iter()
+            .zip(share.rand().batch())
+            .enumerate()
+            .any(|(b, (s, r))| !self.verify_coeffs_on(b, x, &[*s, *r]))
+    }
+
     pub fn verify(&self, share: &Share) -> bool {
         let i = share.index() as usize;
         !share
@@ -163,3 +206,13 @@ impl Commitment {
         })
     }
 }
+
+impl AddAssign<&Commitment> for Commitment {
+    fn add_assign(&mut self, other: &Commitment) {
+        for (line, other) in self.comms.iter_mut().zip(other.all()) {
+            for (c, o) in line.iter_mut().zip(other) {
+                c.add_assign(o);
+            }
+        }
+    }
+}
@@ -58,16 +58,30 @@ impl RSDecoder {
     }
 }
 
-pub fn reed_solomon_encode(mut message: Bytes, n: usize, t: usize) -> (Vec<Bytes>, RSDecoderData) {
-    let original_message_len = message.len();
-    while message.len() % t != 0 {
-        message.push(0)
-    }
-    let share_size = message.len() / t;
+/// Parameters of the encoding of a message of `len` bytes in n shares, t of them decoding it
+pub fn reed_solomon_datas(len: usize, n: usize, t: usize) -> RSDecoderData {
+    let share_size = len.div_ceil(t);
     let mut pow_2_size = smallest_power_of_2_greater_or_eq_than(share_size).0;
     if pow_2_size < 64 {
         pow_2_size = 64
     }
+    RSDecoderData {
+        original_message_len: len,
+        n,
+        t,
+        share_size,
+        pow_2_size,
+    }
+}
+
+pub fn reed_solomon_encode(mut message: Bytes, n: usize, t: usize) -> (Vec<Bytes>, RSDecoderData) {
+    let datas = reed_solomon_datas(message.len(), n, t);
+    let RSDecoderData {
+        share_size,
+        pow_2_size,
+        ..
+    } = datas;
+    message.resize(share_size * t, 0);
     let mut encoder = ReedSolomonEncoder::new(t, n, pow_2_size).unwrap();
 
     for mut i in 0..t {
@@ -82,12 +96,5 @@ pub fn reed_solomon_encode(mut message: Bytes, n: usize, t: usize) -> (Vec<Bytes
         .recovery_iter()
         .map(|s| s.to_vec())
         .collect::<Vec<Bytes>>();
-    let datas = RSDecoderData {
-        original_message_len,
-        n,
-        t,
-        share_size,
-        pow_2_size,
-    };
     (shares, datas)
 }
@@ -108,6 +108,16 @@ impl Share {
     }
 }
 
+impl AddAssign<&Share> for Share {
+    fn add_assign(&mut self, other: &Share) {
+        for (mine, other) in self.shares.iter_mut().zip(other.share()) {
+            for (s, o) in mine.batch_mut().iter_mut().zip(other.batch()) {
+                s.add_assign(o);
+            }
+        }
+    }
+}
+
 impl PartialEq for Share {
     fn eq(&self, other: &Share) -> bool {
         self.index() == other.index()
@@ -1,8 +1,11 @@
 use super::{
     crypto_lib::{
+        crypto_blstrs::{poly_commit::feldman::BlstrsFeldman, polynomial::BlstrsPolynomial},
         evaluation_domain::BatchEvaluationDomain,
         fft::fft,
+        hash_to_scalar,
         lagrange::{lagrange_coefficients, lagrange_coefficients_at_zero},
+        random_scalar,
         vss::{
             keys::InputSecret,
             ni_vss::dealing::{create_dealing, verify_dealing},
@@ -15,9 +18,14 @@ use super::{
 };
 use crate::node::configuration::Configuration;
 use aptos_crypto::Signature;
-use blstrs::{G1Projective, Scalar};
+use blstrs::{Bls12, G1Projective, G2Projective, Scalar};
 use ff::Field;
-use group::Group;
+use global_lib::{
+    crypto::{ElGamalCiphertext, DST_THRESHOLD_DECRYPT},
+    messages::Algo,
+};
+use group::{Curve, Group};
+use pairing::Engine;
 use rand::thread_rng;
 use std::{collections::HashMap, ops::Mul};
 
@@ -96,26 +104,122 @@ pub fn verify_encryption(
     })
 }
 
+/// h^r of a share decrypted without its randomness, with the proof that its node decrypted it
+pub type RandComm = (G1Projective, DleqProof);
+/// Encryption (g^rb, h^r * pk^rb) of the h^r of a share, taken from the dealing
+pub type EncRandComm = (G1Projective, G1Projective);
+
+/// The encrypted h^r of the i-th receiver of every encryption of the batch.
+pub fn enc_rand_comms(encs: &[Encryption], i: usize) -> Vec<EncRandComm> {
+    encs.iter().map(|e| (e.r_bb, e.enc_rr[i])).collect()
+}
+
+/// Decrypts the h^r of the share of the node, with the proof that it used the key of the node.
+pub fn decrypt_rand_comms(
+    sc: &Configuration,
+    enc_rand_comms: &[EncRandComm],
+    skey: &Scalar,
+) -> Vec<RandComm> {
+    let g = sc.base()[0];
+    enc_rand_comms
+        .iter()
+        .map(|(r_bb, enc_rr)| (enc_rr - r_bb * skey, dleq_prove(&g, r_bb, skey)))
+        .collect()
+}
+
+/// Checks a share decrypted without its randomness against the commitment, its h^r being proven
+/// to be the ones encrypted in the dealing for the public key of its node.
+pub fn verify_decrypted_share(
+    sc: &Configuration,
+    comm: &Commitment,
+    share: &Share,
+    pkey: &G1Projective,
+    enc_rand_comms: &[EncRandComm],
+    rand_comms: &[RandComm],
+) -> bool {
+    let g = sc.base()[0];
+    let shares = share.only_share().batch();
+    shares.len() == comm.batch_size()
+        && rand_comms.len() == comm.batch_size()
+        && enc_rand_comms.len() == comm.batch_size()
+        && enc_rand_comms
+            .iter()
+            .zip(rand_comms)
+            .zip(shares)
+            .enumerate()
+            .all(|(b, (((r_bb, enc_rr), (rand_comm, proof)), s))| {
+                dleq_verify(&g, pkey, r_bb, &(enc_rr - rand_comm), proof)
+                    && comm.verify_with_rand_comm_on(b, share.uindex(), s, rand_comm)
+            })
+}
+
 pub fn interpolate(
     sc: &Configuration,
+    comm: &Commitment,
     shares: &HashMap<u16, Share>,
     secrets: &Option<Vec<Secret>>,
 ) -> bool {
-    let selected = shares.keys().map(|i| *i as usize).collect::<Vec<_>>();
-    let lagr = lagrange_coefficients_at_zero(sc.get_batch_evaluation_domain(), &selected);
-    !(0..sc.batch_size()).any(|b_index| {
-        let mut s = Scalar::zero();
-        let mut r = Scalar::zero();
+    interpolate_with_rand_comms(sc, comm, shares, &HashMap::new(), secrets)
+}
 
-        for (i, j) in selected.iter().enumerate() {
-            s += lagr[i] * shares[&(*j as u16)].only_share().get(b_index);
-            r += lagr[i] * shares[&(*j as u16)].rand().get(b_index);
-        }
-        if let Some(secrets) = secrets {
-            secrets[b_index] != s
+/// Interpolates the secrets and their randomness at the points holding them, and checks them
+/// against the commitment there, so that every node checks the reconstruction and not only the
+/// dealer. A share decrypted without its randomness comes with the commitments h^r of it.
+pub fn interpolate_with_rand_comms(
+    sc: &Configuration,
+    comm: &Commitment,
+    shares: &HashMap<u16, Share>,
+    rand_comms: &HashMap<u16, Vec<G1Projective>>,
+    secrets: &Option<Vec<Secret>>,
+) -> bool {
+    let dom = sc.get_batch_evaluation_domain();
+    let k = sc.packing() as usize;
+    let alphas = if sc.is_packed() {
+        (0..k)
+            .map(|j| dom.get_root_of_unity(sc.index_secret(j)))
+            .collect()
+    } else {
+        vec![Scalar::zero()]
+    };
+    let coefficients = |points: &[usize]| {
+        if sc.is_packed() {
+            lagrange_coefficients(dom, points, &alphas)
         } else {
-            false
+            vec![lagrange_coefficients_at_zero(dom, points)]
         }
+    };
+    let selected = shares.keys().map(|i| *i as usize).collect::<Vec<_>>();
+    let lagr = coefficients(&selected);
+    // Feldman and Haven commit to the coefficients, the others to the evaluations of the nodes
+    let comm_lagr = (!matches!(sc.algo(), Algo::Feldman | Algo::Haven))
+        .then(|| coefficients(&(0..sc.get_threshold()).collect::<Vec<_>>()));
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let base = comm.base();
+    !(0..sc.batch_size()).any(|b| {
+        let line = &comm.all()[b];
+        lagr.iter().enumerate().any(|(j, lagr)| {
+            let mut s = Scalar::zero();
+            let mut r = Scalar::zero();
+            let mut r_comm = G1Projective::identity();
+            for (i, index) in selected.iter().enumerate() {
+                let share = &shares[&(*index as u16)];
+                s += lagr[i] * share.only_share().get(b);
+                match rand_comms.get(&(*index as u16)) {
+                    Some(rand_comms) => r_comm += rand_comms[b] * lagr[i],
+                    None => r += lagr[i] * share.rand().get(b),
+                }
+            }
+            let committed = match &comm_lagr {
+                Some(comm_lagr) => {
+                    G1Projective::multi_exp(&line[..comm_lagr[j].len()], &comm_lagr[j])
+                }
+                None => feldman.evaluate_commitment(line, &alphas[j]),
+            };
+            let wrong_secret = secrets
+                .as_ref()
+                .is_some_and(|secrets| secrets[b * k + j] != s);
+            wrong_secret || committed != base[0] * s + base[1] * r + r_comm
+        })
     })
 }
 
@@ -283,14 +387,13 @@ pub fn kzg_interpolate_all(sc: &Configuration, shares: &mut [Share]) -> Vec<Shar
         .collect()
 }
 
-pub fn kzg_interpolate_all_without_proofs(sc: &Configuration, shares: &[Share]) -> Vec<Share> {
-    let n = sc.get_evaluation_domain().N;
-    let res = complete_evaluations(n, shares, sc.get_batch_evaluation_domain());
-
-    res.into_iter()
-        .take(sc.n() as usize)
+/// Completes the evaluations of a KZG committed line on every node with their proofs, from enough
+/// of them.
+pub fn kzg_complete_line(sc: &Configuration, shares: &[Share]) -> Vec<Share> {
+    complete_evaluations_with_proofs(sc.n() as usize, shares, sc.get_batch_evaluation_domain())
+        .into_iter()
         .enumerate()
-        .map(|(i, (s, _))| Share::kzg_new_without_proof(i as u16, s))
+        .map(|(i, (s, w))| Share::kzg_new(i as u16, s, w))
         .collect()
 }
 
@@ -348,6 +451,22 @@ pub fn kzg_interpolate_specific_share(
     Share::new(i as u16, s, Vec::new())
 }
 
+/// Interpolates the evaluation at the index i of a KZG committed line, with its proof interpolated
+/// from the proofs of the shares.
+pub fn kzg_interpolate_specific_share_with_proof(
+    batch_dom: &BatchEvaluationDomain,
+    shares: &[Share],
+    i: usize,
+) -> Share {
+    let selected = shares.iter().map(|s| s.uindex()).collect::<Vec<_>>();
+    let evals = shares.iter().map(|s| *s.first_share()).collect::<Vec<_>>();
+    let proofs = shares.iter().map(|s| *s.proof()).collect::<Vec<_>>();
+    let alpha = batch_dom.get_root_of_unity(i);
+    let (s, w) = interpolate_on_single_with_proofs(&evals, &selected, &[alpha], &proofs, batch_dom)
+        .remove(0);
+    Share::kzg_new(i as u16, s, w)
+}
+
 pub fn interpolate_specific_share(
     batch_dom: &BatchEvaluationDomain,
     shares: &[Share],
@@ -374,21 +493,52 @@ pub fn interpolate_specific_share(
     Share::new(i as u16, s, g)
 }
 
+pub fn interpolate_specific_share_on_zero(
+    batch_dom: &BatchEvaluationDomain,
+    shares: &[Share],
+    i: u16,
+) -> Share {
+    let selected = shares.iter().map(|s| s.uindex()).collect::<Vec<_>>();
+    let lagr = lagrange_coefficients_at_zero(batch_dom, &selected);
+    let b = shares[0].only_share().len();
+    let mut s = Vec::with_capacity(b);
+    let mut r = Vec::with_capacity(b);
+    for b_index in 0..b {
+        let mut i_s = Scalar::zero();
+        let mut i_r = Scalar::zero();
+        for (j, share) in shares.iter().enumerate() {
+            i_s += lagr[j] * share.only_share().get(b_index);
+            i_r += lagr[j] * share.rand().get(b_index);
+        }
+        s.push(i_s);
+        r.push(i_r);
+    }
+    Share::new(i, s, r)
+}
+
 pub fn compute_comm_and_shares(sc: &Configuration) -> (Commitment, Vec<Share>, Vec<Secret>) {
-    let n = sc.n() as usize;
+    let n = sc.nb_points() as usize;
     let b = sc.batch_size();
     let mut secrets = Vec::new();
     let mut shares: Vec<Vec<Scalar>> = vec![Vec::with_capacity(b); n];
     let mut rands: Vec<Vec<Scalar>> = vec![Vec::with_capacity(b); n];
     let mut comm = Commitment::new(*sc.base());
-    for _ in 0..b {
+    for i in 0..b {
         let mut rng = thread_rng();
         let s = InputSecret::new_random(sc.get_threshold(), true, &mut rng);
-        secrets.push(s.get_secret_a());
-        let f = s.get_secret_f();
+        let mut f = s.get_secret_f().clone();
+        if let Some(secret) = sc.given_secret(i) {
+            f[0] = secret;
+        }
         let r = s.get_secret_r();
 
-        let mut f_evals = fft(f, sc.get_evaluation_domain());
+        let mut f_evals = fft(&f, sc.get_evaluation_domain());
+        if sc.is_packed() {
+            let packed = sc.index_secret(0)..sc.index_secret(sc.packing() as usize);
+            secrets.extend_from_slice(&f_evals[packed]);
+        } else {
+            secrets.push(f[0]);
+        }
         f_evals.truncate(n);
 
         let mut r_evals = fft(r, sc.get_evaluation_domain());
@@ -411,3 +561,664 @@ pub fn compute_comm_and_shares(sc: &Configuration) -> (Commitment, Vec<Share>, V
         secrets,
     )
 }
+
+/// Share sent to the node i by the dealer, emptied or altered by its attack. other is the share
+/// of the node in the polynomial proposed by an equivocating dealer
+pub fn dealt_share(sc: &Configuration, i: u16, mut share: Share, other: Option<&Share>) -> Share {
+    if sc.omits(i) {
+        share = Share::empty(sc.batch_size());
+        share.set_index(i);
+    } else if sc.corrupts_share(i) {
+        share.corrupt();
+    } else if let Some(other) = other.filter(|_| sc.equivocates_with(i)) {
+        share = other.clone();
+    }
+    share
+}
+
+pub fn compute_feldman_comm_and_shares(
+    sc: &Configuration,
+) -> (Commitment, Vec<Share>, Vec<Secret>) {
+    feldman_comm_and_shares(sc, sc.given_secrets())
+}
+
+/// Feldman sharing of zero, added to the existing shares to refresh them.
+pub fn compute_feldman_zero_comm_and_shares(sc: &Configuration) -> (Commitment, Vec<Share>) {
+    compute_feldman_comm_and_shares_of(sc, &vec![Scalar::zero(); sc.batch_size()])
+}
+
+/// Feldman sharing of the given secrets, the batch being made of them.
+pub fn compute_feldman_comm_and_shares_of(
+    sc: &Configuration,
+    secrets: &[Secret],
+) -> (Commitment, Vec<Share>) {
+    let (comm, shares, _) = feldman_comm_and_shares(sc, Some(secrets));
+    (comm, shares)
+}
+
+fn feldman_comm_and_shares(
+    sc: &Configuration,
+    given: Option<&[Secret]>,
+) -> (Commitment, Vec<Share>, Vec<Secret>) {
+    let n = sc.nb_points() as usize;
+    let b = given.map_or(sc.batch_size(), |given| given.len());
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let mut secrets = Vec::new();
+    let mut shares: Vec<Vec<Scalar>> = vec![Vec::with_capacity(b); n];
+    let mut comm = Commitment::new(*sc.base());
+    for i in 0..b {
+        let mut rng = thread_rng();
+        let s = InputSecret::new_random(sc.get_threshold(), false, &mut rng);
+        let mut f = s.get_secret_f().clone();
+        if let Some(given) = given {
+            f[0] = given[i];
+        }
+        secrets.push(f[0]);
+        comm.push_line(feldman.commit(&BlstrsPolynomial::from(f.clone())).unwrap());
+
+        let mut f_evals = fft(&f, sc.get_evaluation_domain());
+        f_evals.truncate(n);
+        for (i, s) in shares.iter_mut().enumerate() {
+            s.push(f_evals[i]);
+        }
+    }
+    (
+        comm,
+        shares
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| Share::new(i as u16, s, vec![Scalar::zero(); b]))
+            .collect(),
+        secrets,
+    )
+}
+
+/// Checks that the dealing of a resharing shares the share of `i`: its constant terms must be the
+/// evaluations at `i` of the commitment of the source committee.
+pub fn verify_reshare_dealing(
+    source: &Configuration,
+    target: &Configuration,
+    comm: &Commitment,
+    i: u16,
+    dealing: &Commitment,
+) -> bool {
+    let feldman = BlstrsFeldman::new(source.degree() as usize, source.base()[0]);
+    let x = source
+        .get_batch_evaluation_domain()
+        .get_root_of_unity(i as usize);
+    dealing.batch_size() == comm.batch_size()
+        && dealing.all().iter().zip(comm.all()).all(|(d, line)| {
+            d.len() == target.get_threshold() && d[0] == feldman.evaluate_commitment(line, &x)
+        })
+}
+
+/// Combines the dealings of the source nodes of `set` into the commitment and the share of the
+/// target committee, with the Lagrange coefficients of the source committee.
+pub fn combine_reshare(
+    source: &Configuration,
+    target: &Configuration,
+    set: &[u16],
+    dealings: &HashMap<u16, Commitment>,
+    sub_shares: &HashMap<u16, Share>,
+) -> (Commitment, Share) {
+    let selected = set.iter().map(|i| *i as usize).collect::<Vec<_>>();
+    let lagr = lagrange_coefficients_at_zero(source.get_batch_evaluation_domain(), &selected);
+    let b = dealings[&set[0]].batch_size();
+    let mut comm = Commitment::new(*target.base());
+    let mut share = vec![Scalar::zero(); b];
+    for (k, s) in share.iter_mut().enumerate() {
+        comm.push_line(
+            (0..target.get_threshold())
+                .map(|c| {
+                    let coeffs = set
+                        .iter()
+                        .map(|i| dealings[i].all()[k][c])
+                        .collect::<Vec<_>>();
+                    G1Projective::multi_exp(&coeffs, &lagr)
+                })
+                .collect(),
+        );
+        for (i, l) in set.iter().zip(lagr.iter()) {
+            *s += sub_shares[i].only_share().get(k) * l;
+        }
+    }
+    let share = Share::new(target.index(), share, vec![Scalar::zero(); b]);
+    (comm, share)
+}
+
+/// Sub-shares of a dealing encrypted to the keys of their nodes, with the g^r of the encryption:
+/// the sub-share of the node i is masked by hashes of pk_i^r.
+pub type EncSubShares = (G1Projective, Vec<Vec<Scalar>>);
+/// Key pk_i^r of the encryption revealed by the node i, with the proof that it used its own secret
+/// key, so that everybody can check that the dealer encrypted it an invalid sub-share.
+pub type SubShareComplaint = (u16, G1Projective, DleqProof); // (dealer, key, proof)
+
+const DST_SUB_SHARE: &[u8] = b"SecretSharingTestbedSubShare";
+
+fn sub_share_masks(key: &G1Projective, b: usize) -> Vec<Scalar> {
+    let key = key.to_compressed();
+    (0..b as u32)
+        .map(|k| hash_to_scalar(&[&key[..], &k.to_le_bytes()].concat(), DST_SUB_SHARE))
+        .collect()
+}
+
+/// Encrypts the sub-shares to the keys of their nodes, given in the order of the shares.
+pub fn encrypt_sub_shares(
+    sc: &Configuration,
+    pkeys: &[G1Projective],
+    shares: &[Share],
+) -> EncSubShares {
+    let r = random_scalar(&mut thread_rng());
+    let encs = shares
+        .iter()
+        .zip(pkeys)
+        .map(|(share, pkey)| {
+            let batch = share.only_share().batch();
+            batch
+                .iter()
+                .zip(sub_share_masks(&(pkey * r), batch.len()))
+                .map(|(s, m)| s + m)
+                .collect()
+        })
+        .collect();
+    (sc.base()[0] * r, encs)
+}
+
+/// Key of the node for the encryption, with the proof that it's the one of its public key.
+pub fn sub_share_key(
+    sc: &Configuration,
+    (r, _): &EncSubShares,
+    skey: &Scalar,
+) -> (G1Projective, DleqProof) {
+    (r * skey, dleq_prove(&sc.base()[0], r, skey))
+}
+
+/// Decrypts the sub-share of the node i with its key, None when the dealing has none for it.
+pub fn decrypt_sub_share((_, encs): &EncSubShares, i: u16, key: &G1Projective) -> Option<Share> {
+    let enc = encs.get(i as usize)?;
+    let batch = enc
+        .iter()
+        .zip(sub_share_masks(key, enc.len()))
+        .map(|(c, m)| c - m)
+        .collect::<Vec<_>>();
+    let b = batch.len();
+    Some(Share::new(i, batch, vec![Scalar::zero(); b]))
+}
+
+/// Whether the key is the one of the node i for the encryption and decrypts a sub-share that
+/// doesn't match the dealing, which proves that its dealer is faulty.
+pub fn verify_sub_share_complaint(
+    sc: &Configuration,
+    dealing: &Commitment,
+    enc: &EncSubShares,
+    i: u16,
+    pkey: &G1Projective,
+    (_, key, proof): &SubShareComplaint,
+) -> bool {
+    dleq_verify(&sc.base()[0], pkey, &enc.0, key, proof)
+        && !decrypt_sub_share(enc, i, key).is_some_and(|share| feldman_verify(sc, dealing, &share))
+}
+
+/// Checks a share against the Feldman commitment, a share or a commitment that doesn't fit the
+/// configuration is invalid.
+pub fn feldman_verify(sc: &Configuration, comm: &Commitment, share: &Share) -> bool {
+    let batch = share.only_share().batch();
+    if share.index() >= sc.nb_points()
+        || batch.len() != comm.batch_size()
+        || comm
+            .all()
+            .iter()
+            .any(|line| line.len() != sc.get_threshold())
+    {
+        return false;
+    }
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let x = sc
+        .get_batch_evaluation_domain()
+        .get_root_of_unity(share.uindex());
+    !batch
+        .iter()
+        .zip(comm.all())
+        .any(|(s, line)| !feldman.verify(line, &x, s, &()))
+}
+
+const DST_THRESHOLD_SIGN: &[u8] = b"SecretSharingTestbedThresholdSign";
+
+fn hash_to_sign(message: &[u8]) -> G2Projective {
+    G2Projective::hash_to_curve(message, DST_THRESHOLD_SIGN, b"")
+}
+
+fn is_valid_bls(
+    base: &G1Projective,
+    pkey: &G1Projective,
+    hash: &G2Projective,
+    sign: &G2Projective,
+) -> bool {
+    Bls12::pairing(&pkey.to_affine(), &hash.to_affine())
+        == Bls12::pairing(&base.to_affine(), &sign.to_affine())
+}
+
+/// One partial BLS signature of the message per secret of the batch.
+pub fn partial_sign(share: &Share, message: &[u8]) -> Vec<G2Projective> {
+    let hash = hash_to_sign(message);
+    share
+        .only_share()
+        .batch()
+        .iter()
+        .map(|s| hash * s)
+        .collect()
+}
+
+/// Checks a partial signature against the share's public key, evaluated from the Feldman commitment.
+pub fn verify_partial_sign(
+    sc: &Configuration,
+    comm: &Commitment,
+    i: u16,
+    message: &[u8],
+    partial: &[G2Projective],
+) -> bool {
+    if i >= sc.nb_points() {
+        return false;
+    }
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let x = sc
+        .get_batch_evaluation_domain()
+        .get_root_of_unity(i as usize);
+    let hash = hash_to_sign(message);
+    partial.len() == comm.batch_size()
+        && comm.all().iter().zip(partial).all(|(line, sign)| {
+            is_valid_bls(
+                &sc.base()[0],
+                &feldman.evaluate_commitment(line, &x),
+                &hash,
+                sign,
+            )
+        })
+}
+
+pub fn combine_partial_signs(
+    sc: &Configuration,
+    partials: &HashMap<u16, Vec<G2Projective>>,
+) -> Vec<G2Projective> {
+    let selected = partials.keys().map(|i| *i as usize).collect::<Vec<_>>();
+    let lagr = lagrange_coefficients_at_zero(sc.get_batch_evaluation_domain(), &selected);
+    (0..sc.batch_size())
+        .map(|b| {
+            let signs = selected
+                .iter()
+                .map(|i| partials[&(*i as u16)][b])
+                .collect::<Vec<_>>();
+            G2Projective::multi_exp(&signs, &lagr)
+        })
+        .collect()
+}
+
+/// Checks the signatures against the public keys, the constant terms of the Feldman commitment.
+pub fn verify_sign(
+    sc: &Configuration,
+    comm: &Commitment,
+    message: &[u8],
+    signs: &[G2Projective],
+) -> bool {
+    let hash = hash_to_sign(message);
+    comm.all()
+        .iter()
+        .zip(signs)
+        .all(|(line, sign)| is_valid_bls(&sc.base()[0], &line[0], &hash, sign))
+}
+
+pub type DleqProof = (G1Projective, G1Projective, Scalar);
+
+fn dleq_challenge(points: [&G1Projective; 6]) -> Scalar {
+    let bytes = points
+        .iter()
+        .flat_map(|p| p.to_compressed())
+        .collect::<Vec<u8>>();
+    hash_to_scalar(&bytes, DST_THRESHOLD_DECRYPT)
+}
+
+// Proves that log_g(x) = log_h(y) = s
+fn dleq_prove(g: &G1Projective, h: &G1Projective, s: &Scalar) -> DleqProof {
+    let w = random_scalar(&mut thread_rng());
+    let (a, b) = (g * w, h * w);
+    let e = dleq_challenge([g, &(g * s), h, &(h * s), &a, &b]);
+    (a, b, w + e * s)
+}
+
+fn dleq_verify(
+    g: &G1Projective,
+    x: &G1Projective,
+    h: &G1Projective,
+    y: &G1Projective,
+    (a, b, z): &DleqProof,
+) -> bool {
+    let e = dleq_challenge([g, x, h, y, a, b]);
+    g * z == a + x * e && h * z == b + y * e
+}
+
+/// One decryption share per ciphertext, with the proof that it uses the committed share.
+pub fn decryption_share(
+    sc: &Configuration,
+    share: &Share,
+    ciphertexts: &[ElGamalCiphertext],
+) -> Vec<(G1Projective, DleqProof)> {
+    let g = sc.base()[0];
+    ciphertexts
+        .iter()
+        .zip(share.only_share().batch())
+        .map(|((c1, _), s)| (c1 * s, dleq_prove(&g, c1, s)))
+        .collect()
+}
+
+pub fn verify_decryption_share(
+    sc: &Configuration,
+    comm: &Commitment,
+    i: u16,
+    ciphertexts: &[ElGamalCiphertext],
+    dshare: &[(G1Projective, DleqProof)],
+) -> bool {
+    if i >= sc.nb_points() {
+        return false;
+    }
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let x = sc
+        .get_batch_evaluation_domain()
+        .get_root_of_unity(i as usize);
+    dshare.len() == ciphertexts.len()
+        && comm.all().iter().zip(ciphertexts.iter().zip(dshare)).all(
+            |(line, ((c1, _), (d, proof)))| {
+                let pkey = feldman.evaluate_commitment(line, &x);
+                dleq_verify(&sc.base()[0], &pkey, c1, d, proof)
+            },
+        )
+}
+
+/// Combines the decryption shares with the Lagrange coefficients and returns the plaintexts.
+pub fn combine_decryption_shares(
+    sc: &Configuration,
+    ciphertexts: &[ElGamalCiphertext],
+    dshares: &HashMap<u16, Vec<G1Projective>>,
+) -> Vec<G1Projective> {
+    let selected = dshares.keys().map(|i| *i as usize).collect::<Vec<_>>();
+    let lagr = lagrange_coefficients_at_zero(sc.get_batch_evaluation_domain(), &selected);
+    ciphertexts
+        .iter()
+        .enumerate()
+        .map(|(b, (_, c2))| {
+            let ds = selected
+                .iter()
+                .map(|i| dshares[&(*i as u16)][b])
+                .collect::<Vec<_>>();
+            c2 - G1Projective::multi_exp(&ds, &lagr)
+        })
+        .collect()
+}
+
+/// Splits the secrets of the sharing into the halves `a` and `b` of the triples, the products of
+/// a previous operation following them in the batch.
+pub fn triple_halves<'a>(sc: &Configuration, share: &'a Share) -> (&'a [Scalar], &'a [Scalar]) {
+    let batch = share.only_share().batch();
+    let half = sc.batch_size() / 2;
+    (&batch[..half], &batch[half..2 * half])
+}
+
+/// Proves that the constant terms of a dealing are `g^(a_i * b_i)`: log_g(g^a_i) = log_B(C) with
+/// `B = g^b_i` evaluated from the commitment of the sharing.
+pub fn product_proofs(sc: &Configuration, comm: &Commitment, share: &Share) -> Vec<DleqProof> {
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let x = sc
+        .get_batch_evaluation_domain()
+        .get_root_of_unity(share.uindex());
+    let (a, b) = triple_halves(sc, share);
+    a.iter()
+        .zip(&comm.all()[a.len()..a.len() + b.len()])
+        .map(|(a, line)| dleq_prove(&sc.base()[0], &feldman.evaluate_commitment(line, &x), a))
+        .collect()
+}
+
+pub fn verify_product(
+    sc: &Configuration,
+    comm: &Commitment,
+    i: u16,
+    dealing: &Commitment,
+    proofs: &[DleqProof],
+) -> bool {
+    let feldman = BlstrsFeldman::new(sc.degree() as usize, sc.base()[0]);
+    let x = sc
+        .get_batch_evaluation_domain()
+        .get_root_of_unity(i as usize);
+    let half = sc.batch_size() / 2;
+    let g = sc.base()[0];
+    comm.batch_size() >= 2 * half
+        && dealing.batch_size() == half
+        && proofs.len() == half
+        && (0..half).all(|k| {
+            let a = feldman.evaluate_commitment(&comm.all()[k], &x);
+            let b = feldman.evaluate_commitment(&comm.all()[half + k], &x);
+            dealing.all()[k].len() == sc.get_threshold()
+                && dleq_verify(&g, &a, &b, &dealing.all()[k][0], &proofs[k])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::crypto_lib::{random_g1_point, random_scalars};
+
+    /// Configuration of the node `index` in a network of n nodes with a third of them corrupted
+    fn test_config(algo: Algo, n: u16, batch_size: u16, index: u16) -> Configuration {
+        use global_lib::config_treatment::fields::{Fields, TypeField};
+        let mut fields = Fields::empty();
+        fields.set(TypeField::N, n);
+        fields.set(TypeField::TDenom, 33);
+        fields.set(TypeField::BatchSize, batch_size);
+        fields.set_algo(algo);
+        let base = [
+            G1Projective::generator(),
+            random_g1_point(&mut thread_rng()),
+        ];
+        Configuration::from_fields(&mut fields, base, index, 0, 0)
+    }
+
+    #[test]
+    fn test_threshold_sign() {
+        let sc = test_config(Algo::Feldman, 4, 2, 0);
+        let (comm, shares, _) = compute_feldman_comm_and_shares(&sc);
+        let message = b"message";
+        let partials = shares
+            .iter()
+            .skip(1)
+            .take(sc.get_threshold())
+            .map(|share| (share.index(), partial_sign(share, message)))
+            .collect::<HashMap<_, _>>();
+        for (i, partial) in &partials {
+            assert!(verify_partial_sign(&sc, &comm, *i, message, partial));
+            assert!(!verify_partial_sign(&sc, &comm, 0, message, partial));
+        }
+        let signs = combine_partial_signs(&sc, &partials);
+        assert!(verify_sign(&sc, &comm, message, &signs));
+        assert!(!verify_sign(&sc, &comm, b"other message", &signs));
+    }
+
+    #[test]
+    fn test_dleq_proof() {
+        let mut rng = thread_rng();
+        let (g, h) = (random_g1_point(&mut rng), random_g1_point(&mut rng));
+        let s = random_scalar(&mut rng);
+        let proof = dleq_prove(&g, &h, &s);
+        assert!(dleq_verify(&g, &(g * s), &h, &(h * s), &proof));
+        assert!(!dleq_verify(
+            &g,
+            &(g * s),
+            &h,
+            &(h * (s + Scalar::one())),
+            &proof
+        ));
+        assert!(!dleq_verify(&h, &(h * s), &g, &(g * s), &proof));
+    }
+
+    #[test]
+    fn test_threshold_decrypt() {
+        let sc = test_config(Algo::Feldman, 4, 2, 0);
+        let (comm, shares, _) = feldman_comm_and_shares(&sc, None);
+        let pkeys = comm.all().iter().map(|line| line[0]).collect::<Vec<_>>();
+        let ciphertexts = global_lib::crypto::elgamal_encrypt(&pkeys, b"input");
+        let mut dshares = HashMap::new();
+        for share in shares.iter().skip(1).take(sc.get_threshold()) {
+            let dshare = decryption_share(&sc, share, &ciphertexts);
+            assert!(verify_decryption_share(
+                &sc,
+                &comm,
+                share.index(),
+                &ciphertexts,
+                &dshare
+            ));
+            assert!(!verify_decryption_share(
+                &sc,
+                &comm,
+                0,
+                &ciphertexts,
+                &dshare
+            ));
+            dshares.insert(share.index(), dshare.into_iter().map(|(d, _)| d).collect());
+        }
+        let plaintext = global_lib::crypto::elgamal_plaintext(b"input");
+        for m in combine_decryption_shares(&sc, &ciphertexts, &dshares) {
+            assert_eq!(m, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_combine_reshare() {
+        let source = test_config(Algo::Feldman, 4, 2, 0);
+        let (comm, shares, secrets) = feldman_comm_and_shares(&source, None);
+        let set = [3, 1];
+        let mut dealings = HashMap::new();
+        let mut sub_shares = vec![HashMap::new(); 7];
+        for i in set {
+            let target = test_config(Algo::Feldman, 7, 2, 0);
+            let batch = shares[i as usize].only_share().batch();
+            let (dealing, dealt) = compute_feldman_comm_and_shares_of(&target, batch);
+            assert!(verify_reshare_dealing(&source, &target, &comm, i, &dealing));
+            assert!(!verify_reshare_dealing(
+                &source, &target, &comm, 0, &dealing
+            ));
+            dealings.insert(i, dealing);
+            for (j, share) in dealt.into_iter().enumerate() {
+                sub_shares[j].insert(i, share);
+            }
+        }
+        let mut new_shares = HashMap::new();
+        for (j, sub_shares) in sub_shares.iter().enumerate() {
+            let target = test_config(Algo::Feldman, 7, 2, j as u16);
+            let (new_comm, share) = combine_reshare(&source, &target, &set, &dealings, sub_shares);
+            assert!(feldman_verify(&target, &new_comm, &share));
+            for (new, old) in new_comm.all().iter().zip(comm.all()) {
+                assert_eq!(new[0], old[0]);
+            }
+            new_shares.insert(j as u16, share);
+        }
+        let target = test_config(Algo::Feldman, 7, 1, 0);
+        assert_eq!(interpolate_on_zero(&target, &new_shares), secrets[0]);
+    }
+
+    #[test]
+    fn test_interpolate_with_rand_comms() {
+        let mut rng = thread_rng();
+        let sc = test_config(Algo::Haven, 4, 1, 0);
+        let [g, h] = *sc.base();
+        let f = random_scalars(sc.get_threshold(), &mut rng);
+        let r = random_scalars(sc.get_threshold(), &mut rng);
+        let eval = |poly: &[Scalar], i: u16| {
+            let x = sc
+                .get_batch_evaluation_domain()
+                .get_root_of_unity(i as usize);
+            poly.iter().rev().fold(Scalar::zero(), |acc, c| acc * x + c)
+        };
+        let mut comm = Commitment::new(*sc.base());
+        comm.push_line(f.iter().zip(&r).map(|(f, r)| g * f + h * r).collect());
+        let mut shares = (1..=sc.get_threshold() as u16)
+            .map(|i| (i, Share::new(i, vec![eval(&f, i)], vec![eval(&r, i)])))
+            .collect::<HashMap<_, _>>();
+        let secrets = Some(vec![f[0]]);
+        assert!(interpolate(&sc, &comm, &shares, &secrets));
+        assert!(!interpolate(
+            &sc,
+            &comm,
+            &shares,
+            &Some(vec![f[0] + Scalar::one()])
+        ));
+        // The share of 1 is decrypted without its randomness, only h^r is known
+        shares.insert(1, Share::new(1, vec![eval(&f, 1)], vec![Scalar::zero()]));
+        let rand_comms = HashMap::from([(1, vec![h * eval(&r, 1)])]);
+        assert!(!interpolate(&sc, &comm, &shares, &secrets));
+        assert!(interpolate_with_rand_comms(
+            &sc,
+            &comm,
+            &shares,
+            &rand_comms,
+            &secrets
+        ));
+        let rand_comms = HashMap::from([(1, vec![h * eval(&r, 2)])]);
+        assert!(!interpolate_with_rand_comms(
+            &sc,
+            &comm,
+            &shares,
+            &rand_comms,
+            &secrets
+        ));
+    }
+
+    #[test]
+    fn test_sub_share_complaint() {
+        let mut rng = thread_rng();
+        let sc = test_config(Algo::Feldman, 4, 2, 0);
+        let (comm, shares, _) = feldman_comm_and_shares(&sc, None);
+        let skeys = random_scalars(4, &mut rng);
+        let pkeys = skeys.iter().map(|s| sc.base()[0] * s).collect::<Vec<_>>();
+        let mut enc = encrypt_sub_shares(&sc, &pkeys, &shares);
+        // The dealer gives a wrong sub-share to 1
+        enc.1[1][0] += Scalar::one();
+        for (i, skey) in skeys.iter().enumerate() {
+            let (key, proof) = sub_share_key(&sc, &enc, skey);
+            let share = decrypt_sub_share(&enc, i as u16, &key).unwrap();
+            let complaint = (0, key, proof);
+            assert_eq!(feldman_verify(&sc, &comm, &share), i != 1);
+            assert_eq!(
+                verify_sub_share_complaint(&sc, &comm, &enc, i as u16, &pkeys[i], &complaint),
+                i == 1
+            );
+            assert!(!verify_sub_share_complaint(
+                &sc,
+                &comm,
+                &enc,
+                i as u16,
+                &pkeys[(i + 1) % 4],
+                &complaint
+            ));
+        }
+        let released = [shares[0].clone(), shares[3].clone()];
+        let recovered = interpolate_specific_share(sc.get_batch_evaluation_domain(), &released, 1);
+        assert!(feldman_verify(&sc, &comm, &recovered));
+    }
+
+    #[test]
+    fn test_product_proofs() {
+        let sc = test_config(Algo::Feldman, 4, 2, 0);
+        let (comm, shares, _) = feldman_comm_and_shares(&sc, None);
+        // The products of a previous operation follow the halves in the batch
+        let mut triples = comm.clone();
+        triples.push_line(comm.all()[0].clone());
+        for share in &shares {
+            let (a, b) = triple_halves(&sc, share);
+            let (dealing, _) = compute_feldman_comm_and_shares_of(&sc, &[a[0] * b[0]]);
+            let proofs = product_proofs(&sc, &comm, share);
+            let i = share.index();
+            assert!(verify_product(&sc, &comm, i, &dealing, &proofs));
+            assert!(verify_product(&sc, &triples, i, &dealing, &proofs));
+            assert!(!verify_product(&sc, &comm, (i + 1) % 4, &dealing, &proofs));
+            let (wrong, _) = compute_feldman_comm_and_shares_of(&sc, &[a[0] * b[0] + a[0]]);
+            assert!(!verify_product(&sc, &comm, i, &wrong, &proofs));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    break_if_over,
+    crypto::{combine_decryption_shares, decryption_share, verify_decryption_share, DleqProof},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use blstrs::G1Projective;
+use global_lib::{
+    crypto::{elgamal_plaintext, DecryptInput, ElGamalCiphertext},
+    dec, enc,
+    messages::DecryptCommand,
+    try_dec, Wrapped,
+};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{collections::HashMap, io::Write};
+
+type DecryptionShare = Vec<(G1Projective, DleqProof)>;
+
+/// Threshold ElGamal decryption: the interface encrypts its input to the committed secret of each
+/// session, every node sends its decryption share with a DLEQ proof and combines t + 1 valid ones.
+pub async fn decrypt(node: Wrapped<Node>) {
+    let cloned_node = node.clone();
+    let mut node = node.lock().await;
+    let receiver = node.subscribe(NodeMessage::DecryptSenderConst);
+    let (input, mut ciphertexts): DecryptInput = dec!(node.config().step_input());
+    let ciphertexts = ciphertexts.swap_remove(node.session() as usize);
+    let mine = decryption_share(node.config(), node.my_share(), &ciphertexts);
+    let msg = enc!(Decrypt, DecryptCommand::DecryptionShare, mine);
+    tokio::spawn(async move {
+        decryption_share_receiver(cloned_node, receiver, input, ciphertexts, mine).await
+    });
+    if node.is_byz() {
+        return;
+    }
+    node.broadcast(msg, false).await;
+}
+
+async fn decryption_share_receiver(
+    node: Wrapped<Node>,
+    mut receiver: Receiver<NodeMessage>,
+    input: Vec<u8>,
+    ciphertexts: Vec<ElGamalCiphertext>,
+    mine: DecryptionShare,
+) {
+    let (enough, n, index) = {
+        let node = node.lock().await;
+        (node.config().get_threshold(), node.n(), node.index())
+    };
+    let mut dshares = HashMap::new();
+    dshares.insert(index, mine.into_iter().map(|(d, _)| d).collect());
+    loop {
+        let msg = break_if_over!(receiver);
+        if dshares.len() >= enough {
+            continue;
+        }
+        let (i, dshare): (u16, DecryptionShare) = match msg {
+            NodeMessage::DecryptSender(sender, bytes) => match bytes
+                .first()
+                .copied()
+                .and_then(DecryptCommand::from_checked)
+            {
+                Some(DecryptCommand::DecryptionShare) => (sender, try_dec!(bytes[1..], continue)),
+                None => continue,
+            },
+            _ => panic!("Unexpected message"),
+        };
+        if i >= n || dshares.contains_key(&i) {
+            continue;
+        }
+        let valid = {
+            let node = node.lock().await;
+            verify_decryption_share(node.config(), node.get_comm(), i, &ciphertexts, &dshare)
+        };
+        if !valid {
+            log!(node, "Dropped the invalid decryption share of {i}");
+            continue;
+        }
+        dshares.insert(i, dshare.into_iter().map(|(d, _)| d).collect());
+        if dshares.len() == enough {
+            {
+                let node = node.lock().await;
+                let plaintext = elgamal_plaintext(&input);
+                let plaintexts = combine_decryption_shares(node.config(), &ciphertexts, &dshares);
+                if plaintexts.iter().any(|m| *m != plaintext) {
+                    panic!("FAILED TO DECRYPT")
+                }
+            }
+            Node::output(node.clone());
+        }
+    }
+}
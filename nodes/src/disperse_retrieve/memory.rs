@@ -30,7 +30,7 @@ async fn interpolate_remaining_shares(
     hash_set: HashesSet,
     index: usize,
     results: &mut Vec<Vec<u8>>,
-    decoders: &mut Vec<RSDecoder>,
+    decoders: &mut [RSDecoder],
 ) {
     let mut rev_shares = decoders
         .iter_mut()
@@ -157,7 +157,7 @@ pub async fn messages_handler(node: Wrapped<Node>) {
                     decoders = decoders_datas
                         .clone()
                         .into_iter()
-                        .map(|d| RSDecoder::new(d))
+                        .map(RSDecoder::new)
                         .collect()
                 }
                 if saw.contains(&sender) {
@@ -0,0 +1,82 @@
+use crate::{
+    break_if_over,
+    broadcast::broadcast_message_types::BroadcastMessageType,
+    crypto::{compute_feldman_comm_and_shares, compute_feldman_zero_comm_and_shares, Share},
+    node::{node::Node, node_message::NodeMessage},
+};
+use global_lib::{enc, messages::FeldmanCommand, Step, Wrapped};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::sync::Arc;
+
+pub async fn deal(node: Wrapped<Node>) {
+    // Subscribed before the shares are sent, so that no complaint is missed
+    let channel = NodeMessage::FeldmanComplaintConst;
+    let complaints = node.lock().await.subscribe(channel);
+    let shares = {
+        let mut node = node.lock().await;
+        let (comm, shares) = if node.step() == Step::Refresh {
+            compute_feldman_zero_comm_and_shares(node.config())
+        } else {
+            let (comm, shares, secrets) = compute_feldman_comm_and_shares(node.config());
+            node.set_secrets(secrets);
+            (comm, shares)
+        };
+        let empty_share = Share::empty(node.batch_size());
+        let shares = (0..node.n())
+            .map(|i| {
+                let points = node.config().points(i);
+                shares[points.start as usize..points.end as usize].to_vec()
+            })
+            .collect::<Vec<_>>();
+        for (i, node_shares) in shares.iter().enumerate() {
+            let node_shares = if node.config().omits(i as u16) {
+                node_shares
+                    .iter()
+                    .map(|share| {
+                        let mut empty = empty_share.clone();
+                        empty.set_index(share.index());
+                        empty
+                    })
+                    .collect()
+            } else if node.config().corrupts_share(i as u16) {
+                node_shares
+                    .iter()
+                    .map(|share| {
+                        let mut share = share.clone();
+                        share.corrupt();
+                        share
+                    })
+                    .collect()
+            } else {
+                node_shares.clone()
+            };
+            let msg = enc!(Feldman, FeldmanCommand::Share, node_shares);
+            node.contact(i, Arc::new(msg));
+        }
+        node.reliable_broadcast(BroadcastMessageType::Feldman, enc!(comm))
+            .await;
+        shares
+    };
+    answer_complaints(node, complaints, shares).await
+}
+
+async fn answer_complaints(
+    node: Wrapped<Node>,
+    mut receiver: Receiver<NodeMessage>,
+    shares: Vec<Vec<Share>>,
+) {
+    loop {
+        let msg = break_if_over!(receiver);
+        match msg {
+            NodeMessage::FeldmanComplaint(i) => {
+                let shares = match shares.get(i as usize) {
+                    Some(shares) => shares,
+                    None => continue,
+                };
+                let msg = enc!(Feldman, FeldmanCommand::Reveal, shares);
+                node.lock().await.broadcast(msg, false).await;
+            }
+            _ => panic!("A complaint was expected"),
+        }
+    }
+}
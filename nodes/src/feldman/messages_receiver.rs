@@ -0,0 +1,69 @@
+use super::{dealer::deal, receivers::messages_handler};
+use crate::{
+    break_if_over, create_channels,
+    crypto::Share,
+    node::{node::Node, node_message::NodeMessage},
+};
+use global_lib::{messages::FeldmanCommand, select, try_dec, Wrapped};
+
+pub async fn listen_at(node: Wrapped<Node>) {
+    let channel = NodeMessage::FeldmanSenderConst;
+    let mut receiver = node.lock().await.subscribe(channel);
+    if node.lock().await.step().is_sharing() {
+        create_channels!(node, messages_handler);
+        Node::wait_for_channel(&node, NodeMessage::FeldmanEchoConst).await;
+    }
+    loop {
+        let msg = break_if_over!(receiver);
+        match msg {
+            NodeMessage::FeldmanSender(sender, bytes_message) => {
+                let node = node.clone();
+                select!(
+                    FeldmanCommand, bytes_message, node,
+                    Share => new_share,
+                    Echo => new_echo sender,
+                    Complaint => new_complaint sender,
+                    Reveal => new_reveal sender,
+                    ReconstructShare => new_reconstruct_share,
+                );
+            }
+            _ => panic!("Unexpected message"),
+        }
+    }
+}
+
+pub async fn feldman_share(node: Wrapped<Node>) {
+    if node.lock().await.im_dealer() {
+        deal(node).await;
+    }
+}
+
+async fn new_share(node: Wrapped<Node>, bytes: &[u8]) {
+    let shares: Vec<Share> = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::FeldmanShare(shares)).await;
+}
+
+/// The echo is counted for its sender, whatever index it claims
+async fn new_echo(node: Wrapped<Node>, _bytes: &[u8], sender: u16) {
+    Node::wait_and_send(&node, NodeMessage::FeldmanEcho(sender)).await;
+}
+
+/// Only the sender can complain about its shares, the dealer reveals them to everyone and the
+/// others only accept the reveals of the shares of the nodes that complained
+async fn new_complaint(node: Wrapped<Node>, _bytes: &[u8], sender: u16) {
+    Node::wait_and_send(&node, NodeMessage::FeldmanComplaint(sender)).await;
+}
+
+/// Only the dealer can reveal shares
+async fn new_reveal(node: Wrapped<Node>, bytes: &[u8], sender: u16) {
+    if sender != node.lock().await.config().dealer() {
+        return;
+    }
+    let shares: Vec<Share> = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::FeldmanReveal(shares)).await;
+}
+
+async fn new_reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
+    let shares: Vec<Share> = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::FeldmanReconstructShare(shares)).await;
+}
@@ -0,0 +1,6 @@
+mod dealer;
+mod messages_receiver;
+mod receivers;
+pub mod reconstruct;
+
+pub use messages_receiver::{feldman_share, listen_at as feldman_listen};
@@ -0,0 +1,130 @@
+use crate::{
+    break_if_over,
+    crypto::{feldman_verify, Commitment, Share},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use blstrs::G1Projective;
+use global_lib::{enc, messages::FeldmanCommand, try_dec, Step, Wrapped};
+use group::Group;
+use std::{collections::HashSet, io::Write};
+
+async fn accept_shares(node: &Wrapped<Node>, shares: Vec<Share>, comm: Commitment) {
+    let mut node = node.lock().await;
+    node.save_shares(shares).await;
+    node.set_comm(comm);
+    if !node.is_byz() {
+        let msg = enc!(Feldman, FeldmanCommand::Echo, node.index());
+        node.broadcast(msg, true).await;
+    }
+}
+
+/// A refresh adds the dealt sharing to the stored one, its constant terms must be the identity
+fn is_zero_sharing(comm: &Commitment) -> bool {
+    comm.all()
+        .iter()
+        .all(|line| line.first() == Some(&G1Projective::identity()))
+}
+
+pub async fn messages_handler(node: Wrapped<Node>) {
+    let mut receiver = node.lock().await.subscribe_multiple(&[
+        NodeMessage::FeldmanShareConst,
+        NodeMessage::BroadcastFeldmanConst,
+        NodeMessage::FeldmanEchoConst,
+        NodeMessage::FeldmanRevealConst,
+        NodeMessage::FeldmanComplaintConst,
+    ]);
+    let (t, index, config, refresh) = {
+        let node = node.lock().await;
+        let refresh = node.step() == Step::Refresh;
+        (node.t(), node.index(), node.config().clone(), refresh)
+    };
+    let my_points = config.points(index);
+
+    let enough = 2 * t + 1;
+    let mut shares: Option<Vec<Share>> = None;
+    let mut comm: Option<Commitment> = None;
+    let mut revealed = Vec::new();
+    let mut complaints = HashSet::new();
+    let mut echoes = HashSet::new();
+    let mut accepted = false;
+    let mut complained = false;
+    let mut output = false;
+
+    loop {
+        let msg = break_if_over!(receiver);
+        if output {
+            continue;
+        }
+        match msg {
+            NodeMessage::FeldmanShare(s) => shares = Some(s),
+            NodeMessage::BroadcastFeldman(bytes) => {
+                let dealt: Commitment = try_dec!(bytes, continue);
+                if refresh && !is_zero_sharing(&dealt) {
+                    log!(
+                        node,
+                        "The dealer refreshes with a non zero sharing, the dealing failed"
+                    );
+                    output = true;
+                    Node::abort(node.clone());
+                    continue;
+                }
+                comm = Some(dealt)
+            }
+            NodeMessage::FeldmanEcho(i) => {
+                echoes.insert(i);
+            }
+            NodeMessage::FeldmanReveal(s) => revealed.push(s),
+            NodeMessage::FeldmanComplaint(i) => {
+                complaints.insert(i);
+            }
+            _ => unreachable!(),
+        }
+        let commitment = match &comm {
+            Some(commitment) => commitment,
+            None => continue,
+        };
+        if !accepted && !complained && shares.is_some() {
+            let s = shares.take().unwrap();
+            let valid = s.iter().map(|s| s.index()).eq(my_points.clone())
+                && s.iter().all(|s| feldman_verify(&config, commitment, s));
+            if valid {
+                accepted = true;
+                accept_shares(&node, s, commitment.clone()).await;
+            } else {
+                log!(node, "Invalid share received, complaining");
+                complained = true;
+                let msg = enc!(Feldman, FeldmanCommand::Complaint, index);
+                node.lock().await.broadcast(msg, true).await;
+            }
+        }
+        // A reveal waits for the complaint of the node whose shares it holds
+        let (ready, pending): (Vec<_>, Vec<_>) = revealed.drain(..).partition(|s: &Vec<Share>| {
+            complaints
+                .iter()
+                .any(|i| s.iter().map(|s| s.index()).eq(config.points(*i)))
+        });
+        revealed = pending;
+        for s in ready {
+            if !s.iter().all(|s| feldman_verify(&config, commitment, s)) {
+                log!(
+                    node,
+                    "The dealer revealed an invalid share, the dealing failed"
+                );
+                output = true;
+                Node::abort(node.clone());
+                break;
+            }
+            let mine = s.iter().map(|s| s.index()).eq(my_points.clone());
+            if mine && complained && !accepted {
+                log!(node, "My share has been revealed by the dealer");
+                accepted = true;
+                accept_shares(&node, s, commitment.clone()).await;
+            }
+        }
+        if !output && accepted && echoes.len() as u16 >= enough {
+            output = true;
+            Node::output(node.clone());
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use global_lib::{enc, messages::FeldmanCommand, wrap, Wrapped};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{collections::HashMap, io::Write};
+
+use crate::{
+    break_if_over,
+    crypto::{feldman_verify, interpolate, Share},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+
+pub async fn reconstruct(node: Wrapped<Node>) {
+    if node.lock().await.config().private_reconstruct() {
+        return private_reconstruct(node).await;
+    }
+    let cloned_node = node.clone();
+    let mut node = node.lock().await;
+    let receiver = node.subscribe(NodeMessage::FeldmanReconstructShareConst);
+    tokio::spawn(async move { reconstruct_share_receiver(cloned_node, receiver).await });
+    if node.is_byz() {
+        return;
+    }
+    let msg = enc!(Feldman, FeldmanCommand::ReconstructShare, node.my_shares());
+    node.broadcast(msg, false).await;
+}
+
+/// Sends the shares to the interface alone, along with the index of the node and the commitment
+/// they are checked against.
+/// A byzantine node sends corrupted shares that the interface has to reject.
+async fn private_reconstruct(node: Wrapped<Node>) {
+    {
+        let mut node = node.lock().await;
+        let is_byz = node.is_byz();
+        let shares = node
+            .my_shares()
+            .into_iter()
+            .map(|mut share| {
+                if is_byz {
+                    share.corrupt();
+                }
+                (share.index(), share.only_share().batch().clone())
+            })
+            .collect::<Vec<_>>();
+        let msg = enc!((node.index(), shares, node.get_comm().all()));
+        node.set_private_share(msg);
+    }
+    Node::output(node);
+}
+
+async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
+    let (my_shares, enough) = {
+        let node = node.lock().await;
+        (node.my_shares(), node.config().get_threshold())
+    };
+    let set = wrap!(HashMap::<u16, Share>::new());
+    set.lock()
+        .await
+        .extend(my_shares.into_iter().map(|s| (s.index(), s)));
+    loop {
+        let msg = break_if_over!(receiver);
+        if set.lock().await.len() >= enough {
+            continue;
+        }
+        let set = set.clone();
+        let node = node.clone();
+        tokio::spawn(async move {
+            match msg {
+                NodeMessage::FeldmanReconstructShare(shares) => {
+                    let valid = {
+                        let node = node.lock().await;
+                        shares.iter().all(|s| {
+                            s.only_share().batch().len() == node.get_comm().batch_size()
+                                && feldman_verify(node.config(), node.get_comm(), s)
+                        })
+                    };
+                    let mut set = set.lock().await;
+                    if !valid || set.len() >= enough {
+                        if !valid {
+                            log!(node, "Dropped invalid shares");
+                        }
+                        return;
+                    }
+                    let before = set.len();
+                    for share in shares {
+                        set.entry(share.index()).or_insert(share);
+                    }
+                    if before < enough && set.len() >= enough {
+                        let interpolated = {
+                            let node = node.lock().await;
+                            interpolate(node.config(), node.get_comm(), &set, node.get_secrets())
+                        };
+                        if interpolated {
+                            Node::output(node)
+                        } else {
+                            log!(node, "Failed to interpolate the secrets");
+                            Node::abort(node)
+                        }
+                    }
+                }
+                _ => {
+                    panic!("Unexpected message")
+                }
+            }
+        });
+    }
+}
@@ -15,21 +15,24 @@ pub async fn deal(node: Wrapped<Node>) {
     let mut main_comm = Commitment::new(*config.base());
     let mut comms = vec![Commitment::new(*config.base()); n];
     let mut evals = vec![Vec::new(); n];
+    let mut secrets = Vec::with_capacity(config.batch_size());
     {
         let rng = &mut thread_rng();
 
-        for _ in 0..config.batch_size() {
-            let recovery = Polynomial::random(None, config.l() as usize, rng);
+        for i in 0..config.batch_size() {
+            let recovery = Polynomial::random(config.given_secret(i), config.l() as usize, rng);
             let random_recovery = Polynomial::random(None, config.l() as usize, rng);
             main_comm.add(recovery.fields(), random_recovery.fields());
-            let fft_evals = recovery.fft(dom, config.n() as usize);
+            secrets.push(recovery.fields()[0]);
+            let fft_evals = recovery.fft(dom, n);
+            let fft_rands = random_recovery.fft(dom, n);
             let share_polynomials = fft_evals
                 .into_iter()
-                .enumerate()
-                .map(|(i, e)| {
+                .zip(fft_rands)
+                .map(|(e, r)| {
                     (
-                        Polynomial::random_such_that(rng, config.t() as usize, i, e),
-                        Polynomial::random(None, config.t() as usize, rng),
+                        Polynomial::random(Some(e), config.t() as usize, rng),
+                        Polynomial::random(Some(r), config.t() as usize, rng),
                     )
                 })
                 .collect::<Vec<_>>();
@@ -50,6 +53,7 @@ pub async fn deal(node: Wrapped<Node>) {
             });
         }
     }
+    node.set_secrets(secrets);
     let root: Vec<u8> = Vec::new();
     let messages = stream::iter(evals.into_iter().enumerate())
         .map(|(i, evals)| {
@@ -63,7 +67,7 @@ pub async fn deal(node: Wrapped<Node>) {
                         Haven,
                         HavenCommand::Send,
                         SendMessage {
-                            root: root,
+                            root,
                             comms: (comms, main_comm),
                             evals: evals
                                 .into_iter()
@@ -81,8 +85,9 @@ pub async fn deal(node: Wrapped<Node>) {
         .map(|res| res.unwrap())
         .collect::<Vec<_>>()
         .await;
-    let cor = node.dealer_corruption();
-    for (i, message) in messages.into_iter().skip(cor as usize) {
-        node.give_contact(i, message)
+    for (i, message) in messages {
+        if !node.config().omits(i as u16) {
+            node.give_contact(i, message)
+        }
     }
 }
@@ -5,32 +5,42 @@ use super::{
 };
 
 use crate::{
-    break_if_over, create_channels, log,
+    break_if_over, create_channels,
+    crypto::{Commitment, Share},
+    log,
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::HavenCommand, select, Wrapped};
+use global_lib::{messages::HavenCommand, select, try_dec, Wrapped};
 use std::io::Write;
 
 pub async fn listen_at(node: Wrapped<Node>) {
     let channel = NodeMessage::HavenSenderConst;
     let mut receiver = node.lock().await.subscribe(channel);
     let mut handlers = Vec::new();
-    create_channels!(node, messages_handler);
-    Node::wait_for_channel(&node, NodeMessage::HavenEchoConst).await;
+    if node.lock().await.step().is_sharing() {
+        create_channels!(node, messages_handler);
+        Node::wait_for_channel(&node, NodeMessage::HavenEchoConst).await;
+    }
     loop {
         let msg = break_if_over!(receiver);
         match msg {
-            NodeMessage::HavenSender(bytes_message) => {
+            NodeMessage::HavenSender(sender, bytes_message) => {
                 log!(
                     node,
                     "Haven, new message: {:?}",
-                    HavenCommand::from(bytes_message[0])
+                    bytes_message
+                        .first()
+                        .copied()
+                        .and_then(HavenCommand::from_checked)
                 );
                 let node = node.clone();
                 handlers.push(select!(HavenCommand, bytes_message, node,
                         Send => send,
                         Echo => echo,
                         Ready => ready,
+                        ReconstructShare => reconstruct_share,
+                        CommRequest => comm_request sender,
+                        Comm => comm sender,
                 ));
             }
             _ => panic!("Unexpected message"),
@@ -42,7 +52,7 @@ pub async fn listen_at(node: Wrapped<Node>) {
 }
 
 pub async fn send(node: Wrapped<Node>, bytes: &[u8]) {
-    let msg: SendMessage = dec!(bytes);
+    let msg: SendMessage = try_dec!(bytes);
     node.lock()
         .await
         .send_message(NodeMessage::HavenSend(msg))
@@ -50,7 +60,7 @@ pub async fn send(node: Wrapped<Node>, bytes: &[u8]) {
 }
 
 pub async fn echo(node: Wrapped<Node>, bytes: &[u8]) {
-    let msg: EchoMessage = dec!(bytes);
+    let msg: EchoMessage = try_dec!(bytes);
     node.lock()
         .await
         .send_message(NodeMessage::HavenEcho(msg))
@@ -58,13 +68,28 @@ pub async fn echo(node: Wrapped<Node>, bytes: &[u8]) {
 }
 
 pub async fn ready(node: Wrapped<Node>, bytes: &[u8]) {
-    let root: Vec<u8> = dec!(bytes);
+    let root: Vec<u8> = try_dec!(bytes);
     node.lock()
         .await
         .send_message(NodeMessage::HavenReady(root))
         .await;
 }
 
+pub async fn reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
+    let share: Share = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::HavenReconstructShare(share)).await;
+}
+
+/// The commitment is sent back to the sender of the request, whatever index it claims
+pub async fn comm_request(node: Wrapped<Node>, _bytes: &[u8], sender: u16) {
+    Node::wait_and_send(&node, NodeMessage::HavenCommRequest(sender)).await;
+}
+
+pub async fn comm(node: Wrapped<Node>, bytes: &[u8], sender: u16) {
+    let comm: Commitment = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::HavenComm(sender, comm)).await;
+}
+
 pub async fn haven_share(node: Wrapped<Node>) {
     if node.lock().await.im_dealer() {
         deal(node).await;
@@ -2,3 +2,4 @@ pub mod crypto;
 mod dealer;
 pub mod messages_receiver;
 mod receiver;
+pub mod reconstruct;
@@ -1,7 +1,7 @@
 use super::crypto::{EchoMessage, SendMessage};
 use crate::{
     break_if_over,
-    crypto::{scheme::interpolate_specific_share, Commitment, Share},
+    crypto::{scheme::interpolate_specific_share_on_zero, Commitment, Share},
     log,
     node::{node::Node, node_message::NodeMessage},
 };
@@ -12,7 +12,7 @@ use std::io::Write;
 async fn interpolate_and_output(node: Wrapped<Node>, shares: Vec<(usize, Vec<(Scalar, Scalar)>)>) {
     {
         let mut node = node.lock().await;
-        let mut share = interpolate_specific_share(
+        let share = interpolate_specific_share_on_zero(
             node.dom(),
             &shares
                 .into_iter()
@@ -21,9 +21,8 @@ async fn interpolate_and_output(node: Wrapped<Node>, shares: Vec<(usize, Vec<(Sc
                     Share::new(sender as u16, shares, rands)
                 })
                 .collect::<Vec<_>>(),
-            node.uindex() + 1,
+            node.index(),
         );
-        share.set_index(node.index());
         node.save_share(share).await;
     }
     Node::output(node);
@@ -41,14 +40,9 @@ pub async fn messages_handler(node: Wrapped<Node>) {
         NodeMessage::HavenReadyConst,
         NodeMessage::HavenEchoConst,
     ]);
-    let (n, t, b, index) = {
+    let (n, t, index) = {
         let node = node.lock().await;
-        (
-            node.config().n() as usize,
-            node.t(),
-            node.batch_size(),
-            node.uindex(),
-        )
+        (node.config().n() as usize, node.t(), node.uindex())
     };
 
     let mut shares = Vec::new();
@@ -66,24 +60,26 @@ pub async fn messages_handler(node: Wrapped<Node>) {
         match msg {
             NodeMessage::HavenSend(SendMessage {
                 root,
-                comms: (comms, _),
+                comms: (comms, main_comm),
                 evals,
             }) => {
                 log!(node, "Just received message Send");
                 let mut shares = vec![Vec::new(); n];
-                for i in 0..b as usize {
-                    for (eval, comm) in evals.iter().zip(comms.iter()) {
+                for (b, eval) in evals.iter().enumerate() {
+                    for (i, comm) in comms.iter().enumerate() {
                         let [s, r] = eval.get(i);
-                        comm.verify_on(i, index, &[s, r]);
-                        log!(node, "Successfully verif share {i}");
+                        if !comm.verify_on(b, index, &[s, r]) {
+                            log!(node, "Invalid share {i} in batch {b}");
+                        }
                         shares[i].push((s, r))
                     }
+                    log!(node, "Successfully verif shares of batch {b}");
                 }
                 log!(node, "Successfully verif all shares");
                 let mut node = node.lock().await;
-                for (i, (evals, comm)) in
-                    (shares.into_iter().zip(comms.into_iter()).enumerate()).rev()
-                {
+                // Only the nodes the dealer sent to know the commitment, the others fetch it to reconstruct
+                node.set_comm(main_comm);
+                for (i, (evals, comm)) in (shares.into_iter().zip(comms).enumerate()).rev() {
                     let msg = enc!(
                         Haven,
                         HavenCommand::Echo,
@@ -107,12 +103,10 @@ pub async fn messages_handler(node: Wrapped<Node>) {
                         .await
                         .broadcast(enc!(Haven, HavenCommand::Ready, root), true)
                         .await;
-                } else if ready_count == enough {
-                    if echo_count >= t + 1 {
-                        log!(node, "Outputing (ready path)");
-                        output = true;
-                        interpolate_and_output(node.clone(), shares.drain(..).collect()).await;
-                    }
+                } else if ready_count == enough && echo_count > t {
+                    log!(node, "Outputing (ready path)");
+                    output = true;
+                    interpolate_and_output(node.clone(), std::mem::take(&mut shares)).await;
                 }
             }
             NodeMessage::HavenEcho(EchoMessage {
@@ -146,7 +140,7 @@ pub async fn messages_handler(node: Wrapped<Node>) {
                     log!(node, "Outputing (echo path)");
                     output = true;
 
-                    interpolate_and_output(node.clone(), shares.drain(..).collect()).await;
+                    interpolate_and_output(node.clone(), std::mem::take(&mut shares)).await;
                 }
             }
             _ => unreachable!(),
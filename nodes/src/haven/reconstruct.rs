@@ -0,0 +1,112 @@
+use global_lib::{enc, messages::HavenCommand, Wrapped};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{collections::HashMap, io::Write, sync::Arc};
+
+use crate::{
+    break_if_over,
+    crypto::{interpolate, Commitment, Share},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+
+pub async fn reconstruct(node: Wrapped<Node>) {
+    let cloned_node = node.clone();
+    let mut node = node.lock().await;
+    let receiver = node.subscribe_multiple(&[
+        NodeMessage::HavenReconstructShareConst,
+        NodeMessage::HavenCommRequestConst,
+        NodeMessage::HavenCommConst,
+    ]);
+    let missing_comm = node.get_comm().batch_size() == 0;
+    tokio::spawn(async move { reconstruct_share_receiver(cloned_node, receiver).await });
+    // A node the dealer didn't send to has no commitment to check the shares against
+    if missing_comm {
+        let msg = enc!(Haven, HavenCommand::CommRequest, node.index());
+        node.broadcast(msg, false).await;
+    }
+    if node.is_byz() {
+        return;
+    }
+    let msg = enc!(Haven, HavenCommand::ReconstructShare, node.my_share());
+    node.broadcast(msg, false).await;
+}
+
+/// Buffers the shares until the commitment is known, which a node without it accepts once t + 1
+/// nodes sent the same, then interpolates the secrets from the l + 1 first valid shares.
+async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
+    let (my_share, enough, t, n, own_comm) = {
+        let node = node.lock().await;
+        let comm = node.get_comm().clone();
+        let own_comm = (comm.batch_size() > 0).then_some(comm);
+        (
+            node.my_share().clone(),
+            node.l() as usize + 1,
+            node.t() as usize,
+            node.n(),
+            own_comm,
+        )
+    };
+    let mut comm = own_comm.clone();
+    let mut votes = HashMap::<u16, Commitment>::new();
+    let mut pending = Vec::new();
+    let mut set = HashMap::<u16, Share>::new();
+    set.insert(my_share.index(), my_share);
+    loop {
+        let msg = break_if_over!(receiver);
+        match msg {
+            NodeMessage::HavenCommRequest(sender) => {
+                if let Some(own_comm) = &own_comm {
+                    let msg = enc!(Haven, HavenCommand::Comm, own_comm);
+                    node.lock().await.contact(sender as usize, Arc::new(msg));
+                }
+                continue;
+            }
+            NodeMessage::HavenComm(sender, sent) if comm.is_none() => {
+                let sent = votes.entry(sender).or_insert(sent).clone();
+                if votes.values().filter(|c| **c == sent).count() > t {
+                    comm = Some(sent);
+                }
+            }
+            NodeMessage::HavenComm(..) => continue,
+            NodeMessage::HavenReconstructShare(share) => pending.push(share),
+            _ => panic!("Unexpected message"),
+        }
+        let comm = match &comm {
+            Some(comm) if set.len() < enough => comm,
+            _ => continue,
+        };
+        let roots = {
+            let node = node.lock().await;
+            pending
+                .iter()
+                .map(|share| {
+                    (share.index() < n).then(|| node.dom().get_root_of_unity(share.uindex()))
+                })
+                .collect::<Vec<_>>()
+        };
+        for (share, x) in pending.drain(..).zip(roots) {
+            let i = share.index();
+            let valid = x.is_some_and(|x| {
+                share.only_share().batch().len() == comm.batch_size()
+                    && comm.verify_coeffs(&share, &x)
+            });
+            if !valid {
+                log!(node, "Dropped the invalid share of {i}");
+            } else if set.len() < enough {
+                set.entry(i).or_insert(share);
+            }
+        }
+        if set.len() == enough {
+            let interpolated = {
+                let node = node.lock().await;
+                interpolate(node.config(), comm, &set, node.get_secrets())
+            };
+            if interpolated {
+                Node::output(node.clone())
+            } else {
+                log!(node, "Failed to interpolate the secrets");
+                Node::abort(node.clone())
+            }
+        }
+    }
+}
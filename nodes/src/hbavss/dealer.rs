@@ -1,6 +1,6 @@
 use crate::{
     broadcast::broadcast_message_types::BroadcastMessageType,
-    crypto::{compute_comm_and_shares, Share},
+    crypto::{compute_comm_and_shares, dealt_share},
     node::node::Node,
 };
 use futures::stream::{self, StreamExt};
@@ -8,23 +8,17 @@ use global_lib::{enc, Wrapped};
 
 pub async fn deal(node: Wrapped<Node>) {
     let mut node = node.lock().await;
-    let (comm, mut output, secrets) = compute_comm_and_shares(node.config());
+    let (mut comm, output, secrets) = compute_comm_and_shares(node.config());
     node.set_secrets(secrets);
-    let n = node.n() as usize;
-    let cor = n - node.dealer_corruption() as usize;
-    output.truncate(cor);
-    let empty_share = Share::empty(node.batch_size() as usize);
-    let mut messages: Vec<(usize, Vec<u8>)> = stream::iter((0..n).rev())
-        .map(|i| {
-            let empty_share = empty_share.clone();
+    let other = node
+        .config()
+        .is_equivocating()
+        .then(|| compute_comm_and_shares(node.config()));
+    let mut messages: Vec<(usize, Vec<u8>)> = stream::iter(output.into_iter().enumerate())
+        .map(|(i, share)| {
             let keys = node.get_all_pkey();
-            let share = if i >= cor {
-                let mut share = empty_share.clone();
-                share.set_index(i as u16);
-                share
-            } else {
-                output.pop().unwrap()
-            };
+            let other_share = other.as_ref().map(|(_, shares, _)| &shares[i]);
+            let share = dealt_share(node.config(), i as u16, share, other_share);
             tokio::spawn(async move { (i, enc!(keys[i].blstt_encrypt(enc!(share)))) })
         })
         .buffer_unordered(10)
@@ -32,10 +26,21 @@ pub async fn deal(node: Wrapped<Node>) {
         .collect::<Vec<_>>()
         .await;
 
-    messages.sort_by(|a, b| a.0.cmp(&b.0));
+    messages.sort_by_key(|a| a.0);
 
     node.disperse(messages.into_iter().map(|(_, m)| m).collect())
         .await;
-    node.reliable_broadcast(BroadcastMessageType::HbAvss, enc!(comm))
-        .await
+    if node.config().corrupts_comm() {
+        comm.corrupt()
+    }
+    match other {
+        Some((other, _, _)) => {
+            node.equivocating_broadcast(BroadcastMessageType::HbAvss, enc!(comm), enc!(other))
+                .await
+        }
+        None => {
+            node.reliable_broadcast(BroadcastMessageType::HbAvss, enc!(comm))
+                .await
+        }
+    }
 }
@@ -1,16 +1,19 @@
 use super::{dealer::deal, receivers::wait_for_share, HbAvssAssist, HbAvssComplaint};
 use crate::{
     break_if_over, create_channels,
+    crypto::Share,
     hbavss::receivers::{assist_manager, complaint_manager},
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::HbAvssCommand, select, Wrapped};
+use global_lib::{messages::HbAvssCommand, select, try_dec, Wrapped};
 
 pub async fn listen_at(node: Wrapped<Node>) {
     let mut receiver = node.lock().await.subscribe(NodeMessage::HbAvssSenderConst);
 
-    let cloned_node = node.clone();
-    tokio::spawn(async move { wait_for_share(cloned_node).await });
+    if node.lock().await.step().is_sharing() {
+        let cloned_node = node.clone();
+        tokio::spawn(async move { wait_for_share(cloned_node).await });
+    }
 
     loop {
         let msg = break_if_over!(receiver);
@@ -21,6 +24,7 @@ pub async fn listen_at(node: Wrapped<Node>) {
                     HbAvssCommand, bytes_message, node,
                     Complaint => new_complaint,
                     Assist => new_assist,
+                    ReconstructShare => new_reconstruct_share,
                 );
             }
             _ => panic!("Unexpected message"),
@@ -36,15 +40,20 @@ pub async fn hbavss_share(node: Wrapped<Node>) {
 }
 
 async fn new_complaint(node: Wrapped<Node>, bytes: &[u8]) {
-    let complaint: HbAvssComplaint = dec!(bytes);
+    let complaint: HbAvssComplaint = try_dec!(bytes);
     Node::wait_and_send(&node, NodeMessage::HbAvssComplaint(complaint)).await;
 }
 
 async fn new_assist(node: Wrapped<Node>, bytes: &[u8]) {
-    let assist: HbAvssAssist = dec!(bytes);
+    let assist: HbAvssAssist = try_dec!(bytes);
     Node::wait_and_send(&node, NodeMessage::HbAvssAssist(assist)).await;
 }
 
+async fn new_reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
+    let share: Share = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::HbAvssReconstructShare(share)).await;
+}
+
 async fn init_channels(node: &Wrapped<Node>) {
     create_channels!(node, complaint_manager, assist_manager);
 }
@@ -2,6 +2,7 @@ mod dealer;
 mod messages;
 mod messages_receiver;
 mod receivers;
+pub mod reconstruct;
 
 pub use messages::{Assist as HbAvssAssist, Complaint as HbAvssComplaint};
 
@@ -1,5 +1,5 @@
 use blsttc::serde_impl::SerdeSecret;
-use global_lib::{dec, enc, messages::HbAvssCommand, Wrapped};
+use global_lib::{enc, messages::HbAvssCommand, try_dec, Wrapped};
 
 use crate::{
     break_if_over,
@@ -21,29 +21,34 @@ pub async fn wait_for_share(node: Wrapped<Node>) {
             (node.uindex(), node.my_blstt_skey().clone())
         };
         let bytes = Node::retrieve(&node, index, Some(key)).await;
-        let share: Share = dec!(bytes);
+        let share: Share = try_dec!(bytes, return Node::abort(node));
         node.lock().await.save_share(share).await;
 
-        wait_from_comm(&node).await;
-        process(node).await
+        if wait_from_comm(&node).await {
+            process(node).await
+        } else {
+            Node::abort(node)
+        }
     } else {
         panic!()
     }
 }
 
-pub async fn wait_from_comm(node: &Wrapped<Node>) {
+/// Returns false when the commitment fails the low degree test, the dealing is then rejected
+pub async fn wait_from_comm(node: &Wrapped<Node>) -> bool {
     let channel = NodeMessage::BroadcastHbAvssConst;
     let mut comm_receiver = node.lock().await.subscribe(channel);
     let comm = match comm_receiver.recv().await.unwrap() {
         NodeMessage::BroadcastHbAvss(b) => b,
         _ => panic!("Lightweight broadcast was expected"),
     };
-    let comm: Commitment = dec!(comm);
+    let comm: Commitment = try_dec!(comm, return false);
     let mut node = node.lock().await;
     if !low_deg_test(&comm, node.config()) {
-        panic!("Low deg test didn't pass");
+        return false;
     }
     node.set_comm(comm);
+    true
 }
 
 async fn process(node: Wrapped<Node>) {
@@ -103,31 +108,32 @@ pub async fn complaint_manager(node: Wrapped<Node>) {
         (node.subscribe(msg_kind), node.index())
     };
 
-    let complaint = match receiver.recv().await {
-        Some(complaint) => complaint,
-        None => return,
-    };
-
-    match complaint {
-        NodeMessage::HbAvssComplaint(complaint) => {
-            let share =
-                Node::retrieve(&node, complaint.index as usize, Some(complaint.pkey.0)).await;
-            let share: Share = dec!(share);
-            assert!(!node.lock().await.get_comm().verify(&share));
-            let complaint = enc!(
-                HbAvss,
-                HbAvssCommand::Assist,
-                HbAvssAssist {
-                    index,
-                    pkey: SerdeSecret(node.lock().await.my_blstt_skey().clone()),
+    loop {
+        match break_if_over!(receiver) {
+            NodeMessage::HbAvssComplaint(complaint) => {
+                let share =
+                    Node::retrieve(&node, complaint.index as usize, Some(complaint.pkey.0)).await;
+                let share: Share = try_dec!(share, continue);
+                // A complaint revealing a valid share is dropped, an other one may come
+                if node.lock().await.get_comm().verify(&share) {
+                    eprintln!("The complaint of the node {} is invalid", complaint.index);
+                    continue;
                 }
-            );
-            node.lock().await.broadcast(complaint, false).await;
+                let complaint = enc!(
+                    HbAvss,
+                    HbAvssCommand::Assist,
+                    HbAvssAssist {
+                        index,
+                        pkey: SerdeSecret(node.lock().await.my_blstt_skey().clone()),
+                    }
+                );
+                node.lock().await.broadcast(complaint, false).await;
 
-            Node::output(node.clone());
+                Node::output(node.clone());
+                break;
+            }
+            _ => panic!("A Complaint was expected.."),
         }
-        NodeMessage::Close => receiver.close(),
-        _ => panic!("A Complaint was expected.."),
     }
     loop {
         break_if_over!(receiver);
@@ -138,7 +144,7 @@ pub async fn assist_manager(node: Wrapped<Node>) {
     if !node.lock().await.is_dealer_corrupted() {
         return;
     }
-    let kind = NodeMessage::LightWeightEndOfProcessingConst;
+    let kind = NodeMessage::HbAvssEndOfProcessingConst;
     let mut ok_receiver = node.lock().await.subscribe(kind);
     ok_receiver.recv().await;
 
@@ -158,9 +164,7 @@ pub async fn assist_manager(node: Wrapped<Node>) {
         match msg {
             NodeMessage::HbAvssAssist(assist) => {
                 let share = Node::retrieve(&node, assist.index as usize, Some(assist.pkey.0)).await;
-                let share: Share = dec!(share);
-                assert!(node.lock().await.get_comm().verify(&share));
-
+                let share: Share = try_dec!(share, continue);
                 let mut lnode = node.lock().await;
                 if lnode.get_comm().verify(&share) {
                     shares.push(share);
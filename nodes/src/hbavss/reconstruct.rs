@@ -0,0 +1,71 @@
+use global_lib::{enc, messages::HbAvssCommand, wrap, Wrapped};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{collections::HashMap, io::Write};
+
+use crate::{
+    break_if_over,
+    crypto::{interpolate, Share},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+
+pub async fn reconstruct(node: Wrapped<Node>) {
+    let cloned_node = node.clone();
+    let mut node = node.lock().await;
+    let receiver = node.subscribe(NodeMessage::HbAvssReconstructShareConst);
+    tokio::spawn(async move { reconstruct_share_receiver(cloned_node, receiver).await });
+    if node.is_byz() {
+        return;
+    }
+    let msg = enc!(HbAvss, HbAvssCommand::ReconstructShare, node.my_share());
+    node.broadcast(msg, false).await;
+}
+
+async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
+    let my_share = node.lock().await.my_share().clone();
+    let enough = node.lock().await.t() + 1;
+    let set = wrap!(HashMap::<u16, Share>::new());
+    set.lock().await.insert(my_share.index(), my_share);
+    loop {
+        let msg = break_if_over!(receiver);
+        if set.lock().await.len() as u16 == enough {
+            continue;
+        }
+        let set = set.clone();
+        let node = node.clone();
+        let comm = node.lock().await.get_comm().clone();
+        tokio::spawn(async move {
+            match msg {
+                NodeMessage::HbAvssReconstructShare(share) => {
+                    let i = share.index();
+                    let valid = i < node.lock().await.n()
+                        && share.only_share().batch().len() == comm.batch_size()
+                        && comm.verify(&share);
+                    let mut set = set.lock().await;
+                    if !valid || set.contains_key(&i) || set.len() as u16 == enough {
+                        if !valid {
+                            log!(node, "Dropped the invalid share of {i}");
+                        }
+                        return;
+                    }
+                    set.insert(i, share);
+                    if set.len() as u16 == enough {
+                        let interpolated = {
+                            let node = node.lock().await;
+                            interpolate(node.config(), node.get_comm(), &set, node.get_secrets())
+                        };
+                        if interpolated {
+                            Node::output(node)
+                        } else {
+                            log!(node, "Failed to interpolate the secrets");
+                            Node::abort(node)
+                        }
+                    }
+                }
+                _ => {
+                    panic!("Unexpected message")
+                }
+            }
+        });
+    }
+}
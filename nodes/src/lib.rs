@@ -1,19 +1,28 @@
+pub mod aba;
+pub mod acs;
 pub mod avss_simpl;
 pub mod badger;
-// pub mod beacon;
+pub mod beacon;
 pub mod bingo;
 pub mod broadcast;
 pub mod crypto;
+pub mod decrypt;
 pub mod disperse_retrieve;
+pub mod feldman;
 pub mod hbavss;
 pub mod lightweight;
 pub mod macros;
+pub mod ni_pvss;
 pub mod node;
 pub mod one_sided_vote;
+pub mod reshare;
 // pub mod proc_macro;
 pub mod haven;
 pub mod secure_message_dist;
+pub mod sign;
+pub mod sub_shares;
 pub mod system;
+pub mod triples;
 
 use global_lib::OpId;
 
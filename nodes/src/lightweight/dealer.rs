@@ -1,34 +1,38 @@
 use crate::{
     broadcast::broadcast_message_types::BroadcastMessageType,
-    crypto::{compute_comm_and_shares, Share},
+    crypto::{compute_comm_and_shares, dealt_share},
     node::node::Node,
 };
 use global_lib::{enc, Wrapped};
-use std::collections::VecDeque;
 
 pub async fn deal(node: Wrapped<Node>) {
     let mut node = node.lock().await;
-    let (comm, mut output, secrets) = compute_comm_and_shares(node.config());
+    let (mut comm, output, secrets) = compute_comm_and_shares(node.config());
     node.set_secrets(secrets);
-    let n = node.n() as usize;
-    let cor = n - node.dealer_corruption() as usize;
-    output.truncate(cor);
-    let empty_share = Share::empty(node.batch_size() as usize);
-    let mut messages = VecDeque::with_capacity(n);
-    let mut i = n;
-    while i > 0 {
-        i -= 1;
-        let share = if i >= cor {
-            let mut share = empty_share.clone();
-            share.set_index(i as u16);
-            share
-        } else {
-            output.pop().unwrap()
-        };
-        let buf = enc!(share);
-        messages.push_front(buf)
+    let other = node
+        .config()
+        .is_equivocating()
+        .then(|| compute_comm_and_shares(node.config()));
+    let messages = output
+        .into_iter()
+        .enumerate()
+        .map(|(i, share)| {
+            let other_share = other.as_ref().map(|(_, shares, _)| &shares[i]);
+            enc!(dealt_share(node.config(), i as u16, share, other_share))
+        })
+        .collect();
+    node.distribute(messages).await;
+    if node.config().corrupts_comm() {
+        comm.corrupt()
+    }
+    match other {
+        Some((other, _, _)) => {
+            node.equivocating_broadcast(BroadcastMessageType::LightWeight, enc!(comm), enc!(other))
+                .await
+        }
+        None => {
+            node.reliable_broadcast(BroadcastMessageType::LightWeight, enc!(comm))
+                .await
+        }
     }
-    node.distribute(messages.into_iter().collect()).await;
-    node.reliable_broadcast(BroadcastMessageType::LightWeight, enc!(comm))
-        .await
 }
@@ -3,18 +3,34 @@ use super::{
     receivers::{assist_manager, complaint_manager, report_manager, wait_for_share},
 };
 use crate::{
-    create_channels,
+    break_if_over, create_channels,
+    crypto::Share,
     node::{node::Node, node_message::NodeMessage},
 };
 
-use global_lib::Wrapped;
+use global_lib::{messages::LightWeightCommand, select, try_dec, Wrapped};
 
 pub async fn listen_at(node: Wrapped<Node>) {
     let channel = NodeMessage::LightWeightSenderConst;
-    let mut channel = node.lock().await.subscribe(channel);
+    let mut receiver = node.lock().await.subscribe(channel);
 
-    tokio::spawn(async move { wait_for_share(node).await });
-    channel.recv().await;
+    if node.lock().await.step().is_sharing() {
+        let node = node.clone();
+        tokio::spawn(async move { wait_for_share(node).await });
+    }
+    loop {
+        let msg = break_if_over!(receiver);
+        match msg {
+            NodeMessage::LightWeightSender(bytes_message) => {
+                let node = node.clone();
+                select!(
+                    LightWeightCommand, bytes_message, node,
+                    ReconstructShare => new_reconstruct_share
+                );
+            }
+            _ => panic!("Unexpected message"),
+        }
+    }
 }
 
 pub async fn lightweight_share(node: Wrapped<Node>) {
@@ -24,6 +40,11 @@ pub async fn lightweight_share(node: Wrapped<Node>) {
     }
 }
 
+async fn new_reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
+    let share: Share = try_dec!(bytes);
+    Node::wait_and_send(&node, NodeMessage::LightWeightReconstructShare(share)).await;
+}
+
 async fn init_channels(node: &Wrapped<Node>) {
     create_channels!(node, complaint_manager, assist_manager, report_manager);
 }
@@ -1,5 +1,6 @@
 mod dealer;
 mod messages_receiver;
 mod receivers;
+pub mod reconstruct;
 
 pub use messages_receiver::{lightweight_share, listen_at as lightweight_listen};
@@ -1,4 +1,4 @@
-use global_lib::{dec, Wrapped};
+use global_lib::{try_dec, Wrapped};
 
 use crate::{
     break_if_over,
@@ -14,28 +14,33 @@ pub async fn wait_for_share(node: Wrapped<Node>) {
     let mut message_dis_output = node.lock().await.subscribe(channel);
     let share = message_dis_output.recv().await.unwrap();
     if let NodeMessage::SMDOutput(bytes) = share {
-        let share: Share = dec!(bytes);
+        let share: Share = try_dec!(bytes, return Node::abort(node));
         node.lock().await.save_share(share).await;
-        wait_from_comm(&node).await;
-        process(node).await
+        if wait_from_comm(&node).await {
+            process(node).await
+        } else {
+            Node::abort(node)
+        }
     } else {
         panic!()
     }
 }
 
-pub async fn wait_from_comm(node: &Wrapped<Node>) {
+/// Returns false when the commitment fails the low degree test, the dealing is then rejected
+pub async fn wait_from_comm(node: &Wrapped<Node>) -> bool {
     let channel = NodeMessage::BroadcastLightWeightConst;
     let mut comm_receiver = node.lock().await.subscribe(channel);
     let comm = match comm_receiver.recv().await.unwrap() {
         NodeMessage::BroadcastLightWeight(b) => b,
         _ => panic!("Lightweight broadcast was expected"),
     };
-    let comm: Commitment = dec!(comm);
+    let comm: Commitment = try_dec!(comm, return false);
     let mut node = node.lock().await;
     if !low_deg_test(&comm, node.config()) {
-        panic!("Low deg test didn't pass");
+        return false;
     }
     node.set_comm(comm);
+    true
 }
 
 async fn process(node: Wrapped<Node>) {
@@ -87,24 +92,23 @@ pub async fn complaint_manager(node: Wrapped<Node>) {
         (node.uindex(), node.subscribe(msg_kind))
     };
 
-    let complaint = match receiver.recv().await {
-        Some(complaint) => complaint,
-        None => return,
-    };
-
-    match complaint {
-        NodeMessage::SMDForwardLightWeightComplaint(mem, msg) => {
-            let bytes = mem.lock().await.forward_decode(msg);
-            let share: Share = dec!(bytes);
-            if node.lock().await.get_comm().verify(&share) {
-                panic!("The complaint is invalid")
+    loop {
+        match break_if_over!(receiver) {
+            NodeMessage::SMDForwardLightWeightComplaint(mem, msg) => {
+                let bytes = mem.lock().await.forward_decode(msg);
+                let share: Share = try_dec!(bytes, continue);
+                // A complaint revealing a valid share is dropped, an other one may come
+                if node.lock().await.get_comm().verify(&share) {
+                    eprintln!("The complaint of the share {} is invalid", share.index());
+                    continue;
+                }
+                Node::forward(&node, ForwardTag::Assist(i)).await;
+                Node::forward(&node, ForwardTag::Report(share.uindex())).await;
+                Node::output(node.clone());
+                break;
             }
-            Node::forward(&node, ForwardTag::Assist(i)).await;
-            Node::forward(&node, ForwardTag::Report(share.uindex())).await;
-            Node::output(node);
+            _ => panic!("A Complaint was expected.."),
         }
-        NodeMessage::Close => receiver.close(),
-        _ => panic!("A Complaint was expected.."),
     }
     loop {
         break_if_over!(receiver);
@@ -136,7 +140,7 @@ pub async fn assist_manager(node: Wrapped<Node>) {
         match msg {
             NodeMessage::SMDForwardLightWeightAssist(mem, msg) => {
                 let bytes = mem.lock().await.forward_decode(msg);
-                let share: Share = dec!(bytes);
+                let share: Share = try_dec!(bytes, continue);
                 let mut lnode = node.lock().await;
                 if lnode.get_comm().verify(&share) {
                     shares.push(share);
@@ -176,8 +180,10 @@ pub async fn report_manager(node: Wrapped<Node>) {
         match msg {
             NodeMessage::SMDForwardLightWeightReport(mem, msg) => {
                 let bytes = mem.lock().await.forward_decode(msg);
-                let share: Share = dec!(bytes);
-                assert!(!node.lock().await.get_comm().verify(&share));
+                let share: Share = try_dec!(bytes, continue);
+                if node.lock().await.get_comm().verify(&share) {
+                    eprintln!("The report of the share {} is invalid", share.index());
+                }
             }
             _ => panic!(),
         }
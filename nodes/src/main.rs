@@ -37,12 +37,8 @@ async fn listen_with(listener: TcpListener, heart: NodesHeart) {
         let (mut socket, _) = listener.accept().await.unwrap();
         let heart = heart.clone();
         tokio::spawn(async move {
-            loop {
-                if let Some((message_buf, sender, id)) = get_next_message(&mut socket).await {
-                    heart.new_message(message_buf, sender, id).await;
-                } else {
-                    break;
-                }
+            while let Some((message_buf, sender, id)) = get_next_message(&mut socket).await {
+                heart.new_message(message_buf, sender, id).await;
             }
         });
     }
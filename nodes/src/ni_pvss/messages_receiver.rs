@@ -0,0 +1,58 @@
+use super::{dealer::deal, receivers::verify_and_decrypt, transcript::Transcript};
+use crate::{
+    break_if_over, log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use global_lib::{messages::NiPvssCommand, select, try_dec, Wrapped};
+use std::io::Write;
+
+pub async fn listen_at(node: Wrapped<Node>) {
+    let channel = NodeMessage::NiPvssSenderConst;
+    let mut receiver = node.lock().await.subscribe(channel);
+    transcript_reception_handler(node.clone());
+    loop {
+        let msg = break_if_over!(receiver);
+        match msg {
+            NodeMessage::NiPvssSender(bytes_message) => {
+                let node = node.clone();
+                select!(
+                    NiPvssCommand, bytes_message, node,
+                    ReconstructShare => new_reconstruct_share
+                );
+            }
+            _ => panic!("Unexpected message"),
+        }
+    }
+}
+
+pub async fn ni_pvss_share(node: Wrapped<Node>) {
+    log!(node, "Sharing..");
+    if node.lock().await.im_dealer() {
+        log!(node, "Im the dealer, broadcasting the transcript");
+        deal(node).await;
+    } else {
+        log!(node, "Im not the dealer, doing nothing yet.");
+    }
+}
+
+async fn new_reconstruct_share(node: Wrapped<Node>, bytes: &[u8]) {
+    let (share, rand_comms) = try_dec!(bytes);
+    let msg = NodeMessage::NiPvssReconstructShare(share, rand_comms);
+    let _ = Node::wait_and_send(&node, msg).await;
+}
+
+fn transcript_reception_handler(node: Wrapped<Node>) {
+    tokio::spawn(async move {
+        if !node.lock().await.step().is_sharing() {
+            return;
+        }
+        let channel = NodeMessage::BroadcastNiPvssConst;
+        let mut receiver = node.lock().await.subscribe(channel);
+        log!(node, "Waiting for the transcript...");
+        let transcript: Transcript = match receiver.recv().await.unwrap() {
+            NodeMessage::BroadcastNiPvss(b) => try_dec!(b, return Node::abort(node)),
+            _ => panic!("Unexpected message"),
+        };
+        verify_and_decrypt(node, transcript).await
+    });
+}
@@ -0,0 +1,7 @@
+mod dealer;
+pub mod messages_receiver;
+mod receivers;
+pub mod reconstruct;
+mod transcript;
+
+pub use messages_receiver::{listen_at as ni_pvss_listen, ni_pvss_share};
@@ -0,0 +1,63 @@
+use super::transcript::Transcript;
+use crate::{
+    crypto::{
+        crypto_lib::vss::{
+            common::low_deg_test,
+            ni_vss::{dealing::verify_dealing, encryption::dec_chunks},
+        },
+        decrypt_rand_comms, enc_rand_comms, Share,
+    },
+    log,
+    node::node::Node,
+};
+use blstrs::Scalar;
+use global_lib::Wrapped;
+use std::io::Write;
+
+pub async fn verify_and_decrypt(node: Wrapped<Node>, transcript: Transcript) {
+    log!(node, "Verifying the transcript..");
+    let Transcript { comm, encs } = transcript;
+    let valid = {
+        let mut node = node.lock().await;
+        let keys = node
+            .get_all_pkey()
+            .iter()
+            .map(|k| k.c_key())
+            .collect::<Vec<_>>();
+        let base = comm.base()[1];
+        let valid = low_deg_test(&comm, node.config())
+            && encs.len() == comm.batch_size()
+            && encs
+                .iter()
+                .enumerate()
+                .all(|(b, e)| verify_dealing(&base, &comm.all()[b], &keys, e));
+        if valid {
+            node.log("Transcript verified, decrypting my chunk");
+            for i in 0..node.n() {
+                node.set_mut()
+                    .set_enc_rand_comms(i, enc_rand_comms(&encs, i as usize));
+            }
+            let index = node.uindex();
+            let skey = *node.my_decrypt_skey();
+            let shares: Vec<Scalar> = encs
+                .iter()
+                .map(|e| dec_chunks(&e.ciphertext, skey, index).unwrap())
+                .collect();
+            let rand_comms =
+                decrypt_rand_comms(node.config(), &enc_rand_comms(&encs, index), &skey);
+            let l = shares.len();
+            let share = Share::new(node.index(), shares, vec![Scalar::from(0); l]);
+            node.set_mut().set_rand_comms(rand_comms);
+            node.set_comm(comm);
+            node.save_share(share).await;
+        }
+        valid
+    };
+    if valid {
+        log!(node, "Outputing");
+        Node::output(node);
+    } else {
+        log!(node, "Invalid transcript, the dealing is rejected");
+        Node::abort(node);
+    }
+}
@@ -0,0 +1,110 @@
+use blstrs::G1Projective;
+use global_lib::{enc, messages::NiPvssCommand, wrap, Wrapped};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{collections::HashMap, io::Write};
+
+use crate::{
+    break_if_over,
+    crypto::{interpolate_with_rand_comms, verify_decrypted_share, RandComm, Share},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+
+pub async fn reconstruct(node: Wrapped<Node>) {
+    let cloned_node = node.clone();
+    let mut node = node.lock().await;
+    let receiver = node.subscribe(NodeMessage::NiPvssReconstructShareConst);
+    tokio::spawn(async move { reconstruct_share_receiver(cloned_node, receiver).await });
+    if node.is_byz() {
+        return;
+    }
+    let msg = enc!(
+        NiPvss,
+        NiPvssCommand::ReconstructShare,
+        (
+            node.my_share(),
+            node.set().get_rand_comms().as_ref().unwrap()
+        )
+    );
+    node.broadcast(msg, false).await;
+}
+
+async fn reconstruct_share_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
+    let (my_share, my_rand_comms) = {
+        let node = node.lock().await;
+        (
+            node.my_share().clone(),
+            node.set().get_rand_comms().clone().unwrap(),
+        )
+    };
+    let enough = node.lock().await.t() + 1;
+    let set = wrap!(HashMap::<u16, Share>::new());
+    let rand_set = wrap!(HashMap::<u16, Vec<G1Projective>>::new());
+    rand_set.lock().await.insert(
+        my_share.index(),
+        my_rand_comms.into_iter().map(|(r, _)| r).collect(),
+    );
+    set.lock().await.insert(my_share.index(), my_share);
+    loop {
+        let msg = break_if_over!(receiver);
+        let set = set.clone();
+        let rand_set = rand_set.clone();
+        let node = node.clone();
+        tokio::spawn(async move {
+            match msg {
+                NodeMessage::NiPvssReconstructShare(share, rand_comms) => {
+                    let i = share.index();
+                    let valid = verify_share(&*node.lock().await, &share, &rand_comms);
+                    let mut set = set.lock().await;
+                    if !valid || set.contains_key(&i) || set.len() as u16 == enough {
+                        if !valid {
+                            log!(node, "Dropped the invalid share of {i}");
+                        }
+                        return;
+                    }
+                    let mut rand_set = rand_set.lock().await;
+                    set.insert(i, share);
+                    rand_set.insert(i, rand_comms.into_iter().map(|(r, _)| r).collect());
+                    if set.len() as u16 == enough {
+                        let interpolated = {
+                            let node = node.lock().await;
+                            interpolate_with_rand_comms(
+                                node.config(),
+                                node.get_comm(),
+                                &set,
+                                &rand_set,
+                                node.get_secrets(),
+                            )
+                        };
+                        if interpolated {
+                            Node::output(node)
+                        } else {
+                            log!(node, "Failed to interpolate the secrets");
+                            Node::abort(node)
+                        }
+                    }
+                }
+                _ => {
+                    panic!("Unexpected message")
+                }
+            }
+        });
+    }
+}
+
+/// Decrypted shares only carry h^r, the randomness itself never leaves the ciphertext. The h^r are
+/// proven against their encryption in the transcript.
+fn verify_share(node: &Node, share: &Share, rand_comms: &[RandComm]) -> bool {
+    node.set()
+        .get_enc_rand_comms(share.index())
+        .is_some_and(|enc_rand_comms| {
+            verify_decrypted_share(
+                node.config(),
+                node.get_comm(),
+                share,
+                &node.get_specific_key(share.index()).c_key(),
+                enc_rand_comms,
+                rand_comms,
+            )
+        })
+}
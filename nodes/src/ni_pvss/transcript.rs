@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{data_structures::encryption::Encryption, Commitment};
+
+#[derive(Serialize, Deserialize)]
+pub struct Transcript {
+    pub comm: Commitment,
+    pub encs: Vec<Encryption>,
+}
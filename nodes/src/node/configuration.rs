@@ -1,8 +1,48 @@
 use crate::crypto::{
     crypto_lib::evaluation_domain::{BatchEvaluationDomain, EvaluationDomain},
     data_structures::Base,
+    Secret,
 };
-use global_lib::{config_treatment::fields::Fields, messages::Algo, OpId, Step};
+use global_lib::{
+    config_treatment::fields::{Fields, TypeField},
+    crypto::payload_to_secrets,
+    messages::Algo,
+    ByzBehaviour, CrashPoint, DealerAttack, OpId, Step,
+};
+use std::{ops::Range, time::Duration};
+
+/// The committee holding the shares and the one receiving them during a resharing
+#[derive(Debug, Clone)]
+pub struct Committees {
+    pub source: Configuration,
+    pub target: Configuration,
+    pub offset: u16, // Index of the first node of the target committee
+}
+
+impl Committees {
+    fn new(fields: &Fields, base: Base, index: u16, id: OpId, dealer: u16) -> Self {
+        let mut source = fields.clone();
+        source.set_step(Step::Sharing);
+        let mut target = source.clone();
+        target.set(TypeField::N, fields.new_n());
+        target.set(TypeField::TDenom, fields.new_t_denom());
+        let offset = fields.new_offset();
+        let target_index = index.saturating_sub(offset);
+        Committees {
+            source: Configuration::from_fields(&mut source, base, index, id, dealer),
+            target: Configuration::from_fields(&mut target, base, target_index, id, dealer),
+            offset,
+        }
+    }
+
+    pub fn in_source(&self, index: u16) -> bool {
+        index < self.source.n()
+    }
+
+    pub fn in_target(&self, index: u16) -> bool {
+        index >= self.offset && index - self.offset < self.target.n()
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Configuration {
@@ -11,16 +51,25 @@ pub struct Configuration {
     l: u16,
     n: u16,
     r: u16,
-    dealer_corruption: bool,
+    weights: Vec<u16>,
+    weighted_t: u16,
+    packing: u16,
+    dealer_attack: Option<DealerAttack>,
     id: OpId,
     step: Step,
     index: u16,
     dealer: u16,
-    is_byz: bool,
+    byz_behaviour: Option<ByzBehaviour>,
+    byz_delay: Duration,
+    crash_point: Option<CrashPoint>,
     batch_size: usize,
     dom: EvaluationDomain,
     batch_dom: BatchEvaluationDomain,
     base: Base,
+    step_input: Vec<u8>,
+    given_secrets: Option<Vec<Secret>>,
+    private_reconstruct: bool,
+    committees: Option<Box<Committees>>,
 }
 
 impl Configuration {
@@ -30,26 +79,82 @@ impl Configuration {
         }
 
         assert!(fields.dealer_corruption() <= 1);
-        let domain_size = fields.algo().domain_size(fields.n(), fields.batch_size()) as usize;
+        assert!(
+            fields.dealer_corruption() == 0 || fields.dealer_attack().is_none(),
+            "The dealer corruption can't be combined with a dealer attack"
+        );
+        // The weights, the private reconstruction and the batch of the triples are checked by the
+        // interface with the config
+        fields.check().expect("Unsupported fields");
+        let packing = fields.packing();
+        assert!(
+            packing == 1 || matches!(fields.algo(), Algo::AvssSimpl | Algo::Badger),
+            "Packing is only supported by avss_simpl and badger"
+        );
+        assert!(
+            fields.batch_size().is_multiple_of(packing),
+            "The batch size must be a multiple of the packing"
+        );
+        assert!(
+            fields.secret().is_none() || packing == 1,
+            "A packed sharing can't share a given secret"
+        );
+        let n = fields.nodes_involved();
+        let weights = (0..n).map(|i| fields.weight(i)).collect::<Vec<_>>();
+        let nb_points = weights.iter().sum();
+        let domain_size = match packing {
+            1 => fields.algo().domain_size(nb_points, fields.batch_size()),
+            _ => nb_points + packing,
+        } as usize;
         let batch_dom = BatchEvaluationDomain::new(domain_size);
         let dom = batch_dom.get_subdomain(domain_size);
-        Configuration {
+        let config = Configuration {
             base,
             algo: fields.algo(),
             t: fields.t(),
             l: fields.l(),
-            n: fields.n(),
+            n,
             step: fields.step(),
-            dealer_corruption: fields.dealer_corruption() == 1,
+            // The dealer corruption omits the last t nodes, the attacks only concern the sharing
+            dealer_attack: match fields.dealer_attack() {
+                _ if fields.step() != Step::Sharing => None,
+                Some(attack) => Some(attack.clone()),
+                None => (fields.dealer_corruption() == 1)
+                    .then(|| DealerAttack::Omit((n - fields.t()..n).collect())),
+            },
             r: (2 * fields.t() - fields.l()),
-            is_byz: index < fields.nb_byz(),
-            batch_size: fields.batch_size() as usize,
+            weights,
+            weighted_t: fields.weighted_t(),
+            packing,
+            // The misbehaviour of the dealer is set by the dealer corruption
+            byz_behaviour: fields
+                .byz_behaviour(index)
+                .filter(|b| *b == ByzBehaviour::WrongShares || index != dealer),
+            byz_delay: Duration::from_millis(fields.byz_delay() as u64),
+            crash_point: fields.crash_point(index).filter(|_| index != dealer),
+            batch_size: (fields.batch_size() / packing) as usize,
             id,
             index,
             dealer,
             batch_dom,
             dom,
-        }
+            step_input: match fields.step() {
+                Step::Sign | Step::Decrypt => fields.step_input().to_vec(),
+                _ => Vec::new(),
+            },
+            given_secrets: fields
+                .secret()
+                .filter(|_| fields.step() == Step::Sharing && fields.algo() != Algo::Adkg)
+                .map(|secret| payload_to_secrets(secret, fields.batch_size() as usize)),
+            private_reconstruct: fields.private_reconstruct(),
+            committees: (fields.step() == Step::Reshare)
+                .then(|| Box::new(Committees::new(fields, base, index, id, dealer))),
+        };
+        assert!(
+            !config.is_packed() || config.get_threshold() <= n as usize,
+            "The packed polynomials have a degree too high for the network"
+        );
+        config
     }
 
     pub fn base(&self) -> &Base {
@@ -68,6 +173,27 @@ impl Configuration {
         self.id
     }
 
+    pub fn step_input(&self) -> &[u8] {
+        &self.step_input
+    }
+
+    /// Secrets chosen by the interface, the dealer draws random ones when unset
+    pub fn given_secrets(&self) -> Option<&[Secret]> {
+        self.given_secrets.as_deref()
+    }
+
+    pub fn given_secret(&self, i: usize) -> Option<Secret> {
+        self.given_secrets().map(|secrets| secrets[i])
+    }
+
+    pub fn private_reconstruct(&self) -> bool {
+        self.private_reconstruct
+    }
+
+    pub fn committees(&self) -> &Committees {
+        self.committees.as_ref().expect("Not a resharing")
+    }
+
     pub fn r(&self) -> u16 {
         self.r
     }
@@ -77,7 +203,7 @@ impl Configuration {
     }
 
     pub fn change_byz_comp(&mut self, is_byz: bool) {
-        self.is_byz = is_byz;
+        self.byz_behaviour = is_byz.then_some(ByzBehaviour::WrongShares);
     }
 
     pub fn t(&self) -> u16 {
@@ -89,15 +215,31 @@ impl Configuration {
     }
 
     pub fn is_dealer_corrupted(&self) -> bool {
-        self.dealer_corruption
+        self.dealer_attack.is_some()
     }
 
-    pub fn dealer_corruption(&self) -> u16 {
-        if self.is_dealer_corrupted() {
-            self.t
-        } else {
-            0
-        }
+    /// Whether the node is the dealer and sends nothing to the node i
+    pub fn omits(&self, i: u16) -> bool {
+        self.index == self.dealer
+            && matches!(&self.dealer_attack, Some(DealerAttack::Omit(nodes)) if nodes.contains(&i))
+    }
+
+    /// Whether the share dealt to the node i doesn't match the commitment
+    pub fn corrupts_share(&self, i: u16) -> bool {
+        matches!(&self.dealer_attack, Some(DealerAttack::WrongShares(nodes)) if nodes.contains(&i))
+    }
+
+    pub fn corrupts_comm(&self) -> bool {
+        self.dealer_attack == Some(DealerAttack::BadCommitment)
+    }
+
+    pub fn is_equivocating(&self) -> bool {
+        matches!(self.dealer_attack, Some(DealerAttack::Equivocate(_)))
+    }
+
+    /// Whether the node i is misled with the commitment of another polynomial
+    pub fn equivocates_with(&self, i: u16) -> bool {
+        matches!(&self.dealer_attack, Some(DealerAttack::Equivocate(nodes)) if nodes.contains(&i))
     }
 
     pub fn n(&self) -> u16 {
@@ -108,8 +250,37 @@ impl Configuration {
         self.index
     }
 
+    pub fn weight(&self, i: u16) -> u16 {
+        self.weights[i as usize]
+    }
+
+    /// Total number of evaluation points, n when the nodes are not weighted
+    pub fn nb_points(&self) -> u16 {
+        self.weights.iter().sum()
+    }
+
+    /// Evaluation points held by the node i, one after the other in the order of the nodes
+    pub fn points(&self, i: u16) -> Range<u16> {
+        let start = self.weights[..i as usize].iter().sum();
+        start..start + self.weight(i)
+    }
+
+    /// Whether the node corrupts the content of its messages, the other behaviours only alter the
+    /// way they are sent
     pub fn is_byz(&self) -> bool {
-        self.is_byz
+        self.byz_behaviour == Some(ByzBehaviour::WrongShares)
+    }
+
+    pub fn byz_behaviour(&self) -> Option<ByzBehaviour> {
+        self.byz_behaviour
+    }
+
+    pub fn byz_delay(&self) -> Duration {
+        self.byz_delay
+    }
+
+    pub fn crash_point(&self) -> Option<CrashPoint> {
+        self.crash_point
     }
 
     pub fn dealer(&self) -> u16 {
@@ -117,11 +288,22 @@ impl Configuration {
     }
 
     pub fn degree(&self) -> u32 {
-        (match self.algo {
+        let degree = match self.algo {
             Algo::Badger => self.l(),
             Algo::AvssSimpl => self.t() * 2,
-            _ => self.t(),
-        }) as u32
+            Algo::DualAvssSimpl => self.l(),
+            _ => self.weighted_t,
+        };
+        (degree + self.packing - 1) as u32
+    }
+
+    /// Number of secrets embedded in a polynomial, at the points following the ones of the nodes
+    pub fn packing(&self) -> u16 {
+        self.packing
+    }
+
+    pub fn is_packed(&self) -> bool {
+        self.packing > 1
     }
 
     pub fn is_dual_threshold(&self) -> bool {
@@ -136,6 +318,7 @@ impl Configuration {
         &self.dom
     }
 
+    /// Number of polynomials dealt, the batch divided by the packing
     pub fn batch_size(&self) -> usize {
         self.batch_size
     }
@@ -6,6 +6,8 @@ use super::{
 };
 
 use crate::{
+    aba::{aba_listen, aba_share},
+    acs::{acs_listen, acs_share, agree_on_sessions},
     avss_simpl::{
         messages_receiver::{avss_simpl_share, listen_at as avss_simpl_listen},
         reconstruct::reconstruct as avss_simpl_reconstruct,
@@ -14,12 +16,14 @@ use crate::{
         messages_receiver::{badger_share, listen_at as badger_listen},
         reconstruct::reconstruct as badger_reconstruct,
     },
+    beacon::beacon,
     bingo::{
         messages_receiver::{bingo_share, listen_at as bingo_listen},
         reconstruct::reconstruct as bingo_reconstruct,
     },
     broadcast::{
-        broadcast_message_types::BroadcastMessageType, listener::listen as broadcast_listen,
+        broadcast_message_types::{BroadcastMessageType, Transcript},
+        listener::listen as broadcast_listen,
     },
     crypto::{
         crypto_lib::evaluation_domain::BatchEvaluationDomain,
@@ -30,13 +34,24 @@ use crate::{
         },
         Commitment, Secret, Sign,
     },
+    decrypt::decrypt,
     disperse_retrieve::{disperse_retrieve_listener, get_disperse_messages},
-    haven::messages_receiver::{haven_share, listen_at as haven_listen},
-    hbavss::{hbavss_listen, hbavss_share},
-    lightweight::{lightweight_listen, lightweight_share},
+    feldman::{feldman_listen, feldman_share, reconstruct::reconstruct as feldman_reconstruct},
+    haven::{
+        messages_receiver::{haven_share, listen_at as haven_listen},
+        reconstruct::reconstruct as haven_reconstruct,
+    },
+    hbavss::{hbavss_listen, hbavss_share, reconstruct::reconstruct as hbavss_reconstruct},
+    lightweight::{
+        lightweight_listen, lightweight_share, reconstruct::reconstruct as lightweight_reconstruct,
+    },
+    ni_pvss::{ni_pvss_listen, ni_pvss_share, reconstruct::reconstruct as ni_pvss_reconstruct},
     one_sided_vote::one_sided_vote_listen,
+    reshare::reshare,
     secure_message_dist::{get_secure_message_dis_transcripts, listen as smd_listen, ForwardTag},
+    sign::sign,
     system::{message_interface::SendableMessage, node_sender::ChannelId, summaries::Summaries},
+    triples::triples,
 };
 use aptos_crypto::bls12381::PublicKey as SigningPublicKey;
 use blstrs::Scalar;
@@ -54,21 +69,32 @@ type WritingFuture = WritingHandler<NodeMessage>;
 use global_lib::{
     config_treatment::result_fields::ResultDuration,
     dec, enc,
-    messages::{Algo, BroadcastCommand, NameSpace},
+    messages::{AbaCommand, Algo, BroadcastCommand, NameSpace},
     network::Network,
     process::ProcessTrait,
     settings::VERBOSE,
     task_pool::task::TaskInterface,
-    wrap, NodeId, OpId, Step, Wrapped,
+    wrap, ByzBehaviour, CrashPoint, NodeId, OpId, Step, Wrapped,
 };
+use rand::{thread_rng, Rng};
 use tokio::{
     spawn,
-    sync::mpsc::{channel, Sender},
+    sync::{
+        mpsc::{channel, Sender},
+        watch, Notify,
+    },
     task::JoinHandle,
 };
 
 pub type Handler = JoinHandle<()>;
-pub type Message = Vec<u8>;
+pub type Message = (NodeId, Vec<u8>); // A message received, with its sender as told by the network
+
+/// Ties a session to the agreement on the sessions of its operation
+#[derive(Clone)]
+pub struct SessionLink {
+    over: Sender<u16>, // Votes for the session once it is over on our side
+    agreed: watch::Receiver<Option<Vec<u16>>>, // The agreed sessions, once known
+}
 
 #[macro_export]
 macro_rules! log {
@@ -90,9 +116,18 @@ pub struct Node {
     public_keys: Arc<Vec<PublicKey>>, // The set of public key (gift at init)
     senders: NotifierHub<NodeMessage, ChannelId>, // Sender network
     shares: CryptoSet,                // A container for cryptographics objects
+    coin_set: Option<CryptoSet>,      // Key shares flipping the coins of the binary agreements
+    rounds: Option<u16>,              // Most rounds taken by a binary agreement of the operation
     keypair: KeyPair,                 // The keypair of the node, contains all his private keys
     timer: Instant,                   // Timer used to compute the lifetime of the process
     im_done: bool,                    // Assure that the node will output only once
+    sharing_over: bool,               // In a beacon epoch, the first output only ends the sharing
+    session: Option<u8>,              // Tags our messages when several dealers share concurrently
+    link: Option<SessionLink>,        // Agreement on the session, when the sessions are agreed on
+    private_share: Option<Vec<u8>>,   // Shares sent to the interface by a private reconstruction
+    nb_sent: usize,                   // Messages sent so far, to find when the node crashes
+    crashed: bool,                    // The node reached its crash point or gave up, and stopped
+    crash_notify: Arc<Notify>,        // Wakes the task ending the operation of a crashed node
     result_sender: TaskInterface<NodeProcessOutput>, // Result sender of the process pool
     summaries: Option<Summaries>,
     log_file: Option<File>,
@@ -108,27 +143,228 @@ impl Drop for Node {
     }
 }
 
-impl ProcessTrait<NodeProcessInput, Message, NodeProcessOutput> for Node {
+impl ProcessTrait<Vec<NodeProcessInput>, Message, NodeProcessOutput> for Node {
     fn begin(
+        mut inputs: Vec<NodeProcessInput>,
+        result_sender: TaskInterface<NodeProcessOutput>,
+    ) -> Sender<Message> {
+        let fields = &inputs[0].fields;
+        if fields.agrees_on_sessions() {
+            let identity = (fields.n(), fields.t(), fields.algo(), 0);
+            let step = fields.step();
+            let agreement = inputs.pop().expect("No session to agree on the others");
+            return Self::begin_agreed_sessions(inputs, agreement, result_sender, move |output| {
+                match step {
+                    Step::Refresh => output.into_refresh(identity),
+                    Step::Beacon => output,
+                    _ => output.into_key(identity),
+                }
+            });
+        }
+        if inputs.len() == 1 {
+            return Self::begin_session(inputs.into_iter().next().unwrap(), result_sender, None);
+        }
+        let (output_sender, mut output_receiver) = channel(inputs.len());
+        let nb_sessions = inputs.len();
+        let session_senders = inputs
+            .into_iter()
+            .map(|input| {
+                let interface = TaskInterface::new(result_sender.id(), output_sender.clone());
+                Self::begin_session(input, interface, None)
+            })
+            .collect::<Vec<_>>();
+        spawn(async move {
+            let mut outputs = Vec::with_capacity(nb_sessions);
+            while outputs.len() < nb_sessions {
+                outputs.push(output_receiver.recv().await.unwrap().1);
+            }
+            result_sender
+                .output(NodeProcessOutput::merge(outputs))
+                .await
+                .unwrap();
+        });
+        Self::route_sessions(session_senders)
+    }
+}
+
+impl Node {
+    /// Forwards the messages to the session tagged in their second byte
+    fn route_sessions(session_senders: Vec<Sender<Message>>) -> Sender<Message> {
+        let (message_sender, mut message_receiver) = channel::<Message>(1000);
+        spawn(async move {
+            while let Some((sender, mut bytes_message)) = message_receiver.recv().await {
+                if bytes_message.len() < 2 {
+                    continue;
+                }
+                let session = bytes_message.remove(1) as usize;
+                if let Some(session_sender) = session_senders.get(session) {
+                    let _ = session_sender.send((sender, bytes_message)).await;
+                }
+            }
+        });
+        message_sender
+    }
+
+    /// Runs a session per dealer along with an ACS on the sessions, a node votes for a session once
+    /// it is over on its side. The operation ends once every agreed session is over, the sessions
+    /// left out are not waited for. The sessions learn the agreed ones through their link.
+    fn begin_agreed_sessions(
+        inputs: Vec<NodeProcessInput>,
+        agreement: NodeProcessInput,
+        result_sender: TaskInterface<NodeProcessOutput>,
+        combine: impl FnOnce(NodeProcessOutput) -> NodeProcessOutput + Send + 'static,
+    ) -> Sender<Message> {
+        let nb_sessions = inputs.len();
+        let (output_sender, mut output_receiver) = channel(nb_sessions);
+        let (over_sender, over_receiver) = channel(nb_sessions);
+        let (release, agreed_sessions) = watch::channel(None);
+        let link = SessionLink {
+            over: over_sender,
+            agreed: agreed_sessions,
+        };
+        let mut session_senders = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(session, input)| {
+                let (sender, mut receiver) = channel(1);
+                let output_sender = output_sender.clone();
+                spawn(async move {
+                    if let Some((_, output)) = receiver.recv().await {
+                        let _ = output_sender.send((session as u16, output)).await;
+                    }
+                });
+                let interface = TaskInterface::new(result_sender.id(), sender);
+                Self::begin_session(input, interface, Some(link.clone()))
+            })
+            .collect::<Vec<_>>();
+        drop(link);
+        let (agreed_sender, mut agreed_receiver) = channel(1);
+        let (agreement_sender, mut agreement_receiver) = channel(1);
+        let interface = TaskInterface::new(result_sender.id(), agreement_sender);
+        session_senders.push(Self::begin_agreement(
+            agreement,
+            interface,
+            over_receiver,
+            agreed_sender,
+        ));
+        spawn(async move {
+            let mut outputs = HashMap::new();
+            let mut agreement = None;
+            let mut agreed = None;
+            loop {
+                tokio::select! {
+                    Some((session, output)) = output_receiver.recv() => {
+                        outputs.insert(session, output);
+                    }
+                    Some((_, output)) = agreement_receiver.recv(), if agreement.is_none() => {
+                        agreement = Some(output);
+                        agreed = agreed_receiver.try_recv().ok();
+                        let _ = release.send(agreed.clone());
+                    }
+                    else => break,
+                }
+                // A session aborted by a faulty dealer is not voted for, the node only crashed if
+                // the agreement did
+                let crashed = agreement.as_ref().is_some_and(|output| output.crashed);
+                let over = agreed.as_ref().is_some_and(|agreed: &Vec<u16>| {
+                    agreed.iter().all(|j| outputs.contains_key(j))
+                });
+                if crashed || over {
+                    break;
+                }
+            }
+            let output = Self::end_agreed_sessions(outputs, agreement, agreed, combine);
+            result_sender.output(output).await.unwrap();
+        });
+        Self::route_sessions(session_senders)
+    }
+
+    /// Output of an operation whose sessions were agreed on: the agreed sessions are combined, the
+    /// others and the agreement only account for their messages and rounds. The node crashed if it
+    /// has no agreed set or if one of the agreed sessions crashed.
+    fn end_agreed_sessions(
+        outputs: HashMap<u16, NodeProcessOutput>,
+        agreement: Option<NodeProcessOutput>,
+        agreed: Option<Vec<u16>>,
+        combine: impl FnOnce(NodeProcessOutput) -> NodeProcessOutput,
+    ) -> NodeProcessOutput {
+        let agreed = agreed.unwrap_or_default();
+        let crashed = agreed.is_empty()
+            || agreement.as_ref().is_some_and(|output| output.crashed)
+            || agreed
+                .iter()
+                .any(|j| outputs.get(j).is_none_or(|output| output.crashed));
+        let (sessions, others): (Vec<_>, Vec<_>) = outputs
+            .into_iter()
+            .partition(|(j, _)| !crashed && agreed.contains(j));
+        let output = match crashed {
+            false => combine(NodeProcessOutput::merge(
+                sessions.into_iter().map(|(_, output)| output).collect(),
+            )),
+            true => NodeProcessOutput {
+                crashed,
+                ..Default::default()
+            },
+        };
+        let others = others
+            .into_iter()
+            .map(|(_, output)| output)
+            .chain(agreement)
+            .map(|mut output| {
+                output.share_sets.clear();
+                output
+            });
+        NodeProcessOutput::merge(std::iter::once(output).chain(others).collect())
+    }
+
+    /// Runs the ACS on the sessions, fed with the sessions over on our side
+    fn begin_agreement(
+        input: NodeProcessInput,
+        result_sender: TaskInterface<NodeProcessOutput>,
+        over: tokio::sync::mpsc::Receiver<u16>,
+        agreed: Sender<Vec<u16>>,
+    ) -> Sender<Message> {
+        let (message_sender, message_receiver) = channel(1000);
+        let node = Node::new(input, result_sender);
+        spawn(async move {
+            if node.lock().await.config.crash_point().is_some() {
+                spawn(Self::end_on_crash(node.clone()));
+            }
+            let namespaces = vec![NameSpace::Aba, NameSpace::Acs];
+            Self::start_listeners(node.clone(), namespaces, message_receiver, true).await;
+            agree_on_sessions(node, over, agreed).await
+        });
+        message_sender
+    }
+
+    fn begin_session(
         input: NodeProcessInput,
         result_sender: TaskInterface<NodeProcessOutput>,
+        link: Option<SessionLink>,
     ) -> Sender<Message> {
         let (message_sender, message_receiver) = channel(1000);
         let step = input.fields.step();
         let algo = input.fields.algo();
         let node = Node::new(input, result_sender);
         spawn(async move {
+            node.lock().await.link = link;
+            if node.lock().await.config.crash_point().is_some() {
+                spawn(Self::end_on_crash(node.clone()));
+            }
             Self::start_listener(node.clone(), algo, message_receiver).await;
             match step {
-                Step::Sharing => Self::share(node, algo).await,
+                Step::Sharing | Step::Refresh => Self::share(node, algo).await,
                 Step::Reconstruct => Self::reconstruct(node, algo).await,
+                Step::Beacon => beacon(node, algo).await,
+                Step::Sign => sign(node).await,
+                Step::Decrypt => decrypt(node).await,
+                Step::Reshare => reshare(node).await,
+                Step::Triples => triples(node).await,
             }
         });
         message_sender
     }
-}
 
-impl Node {
     pub fn new(
         input: NodeProcessInput,
         result_sender: TaskInterface<NodeProcessOutput>,
@@ -137,24 +373,37 @@ impl Node {
             id,
             index,
             mut fields,
-            network,
+            mut network,
             shares,
             keypair,
             public_keys,
             dealer,
             base,
+            session,
+            coin_set,
         } = input;
         let log_file = None;
 
         let mut summ = Summaries::new(index);
-        summ.set_n(fields.n() as usize);
+        summ.set_n(fields.nodes_involved() as usize);
+        let config = Configuration::from_fields(&mut fields, base, index, id, dealer);
+        if config.byz_behaviour() == Some(ByzBehaviour::Delaying) {
+            network.set_delay(config.byz_delay());
+        }
+        if let Some(faults) = fields.faults() {
+            network.set_faults(faults.clone());
+        }
+        if let Some(partitions) = fields.partitions() {
+            let start = fields.op_start().expect("The operation has no start time");
+            network.set_partitions(partitions.to_vec(), start);
+        }
         wrap!(Node {
             log: if VERBOSE {
                 Some(File::create(format!("../logs/node_{index}_{id}")).unwrap())
             } else {
                 None
             },
-            config: Configuration::from_fields(&mut fields, base, index, id, dealer),
+            config,
             timer: Instant::now(),
             handlers: Some(Vec::new()),
             network,
@@ -162,14 +411,23 @@ impl Node {
             result_sender,
             senders: NotifierHub::new(),
             shares,
+            coin_set,
+            rounds: None,
             keypair,
             public_keys: Arc::new(public_keys),
             im_done: false,
+            sharing_over: false,
+            session,
+            link: None,
+            private_share: None,
+            nb_sent: 0,
+            crashed: false,
+            crash_notify: Arc::new(Notify::new()),
             log_file
         })
     }
 
-    async fn share(node: Wrapped<Node>, algo: Algo) {
+    pub async fn share(node: Wrapped<Node>, algo: Algo) {
         log!(node, "Sharing with {algo}");
         node.clone().lock().await.push_handler(spawn(async move {
             match algo {
@@ -179,31 +437,52 @@ impl Node {
                 Algo::LightWeight => lightweight_share(node).await,
                 Algo::Badger => badger_share(node).await,
                 Algo::HbAvss => hbavss_share(node).await,
+                Algo::Feldman | Algo::Adkg => feldman_share(node).await,
+                Algo::NiPvss => ni_pvss_share(node).await,
+                Algo::Aba => aba_share(node).await,
+                Algo::Acs => acs_share(node).await,
             }
         }));
     }
 
-    async fn reconstruct(node: Wrapped<Node>, algo: Algo) {
+    pub async fn reconstruct(node: Wrapped<Node>, algo: Algo) {
         log!(node, "Reconstructing with {algo}");
         match algo {
-            Algo::AvssSimpl => avss_simpl_reconstruct(node).await,
+            Algo::AvssSimpl | Algo::DualAvssSimpl => avss_simpl_reconstruct(node).await,
             Algo::Bingo => bingo_reconstruct(node).await,
             Algo::Badger => badger_reconstruct(node).await,
+            Algo::LightWeight => lightweight_reconstruct(node).await,
+            Algo::HbAvss => hbavss_reconstruct(node).await,
+            Algo::Haven => haven_reconstruct(node).await,
+            Algo::Feldman => feldman_reconstruct(node).await,
+            Algo::NiPvss => ni_pvss_reconstruct(node).await,
             _ => {
                 panic!("can't reconstruct with {algo}.")
             }
         }
     }
 
-    fn node_message_from_namespace(namespace: NameSpace, bytes_message: Vec<u8>) -> NodeMessage {
+    fn node_message_from_namespace(
+        namespace: NameSpace,
+        sender: NodeId,
+        bytes_message: Vec<u8>,
+    ) -> NodeMessage {
         match namespace {
             NameSpace::AvssSimpl => NodeMessage::AvssSimplSender(bytes_message),
-            NameSpace::Haven => NodeMessage::HavenSender(bytes_message),
+            NameSpace::Haven => NodeMessage::HavenSender(sender, bytes_message),
             NameSpace::Bingo => NodeMessage::BingoSender(bytes_message),
             NameSpace::LightWeight => NodeMessage::LightWeightSender(bytes_message),
             NameSpace::Badger => NodeMessage::BadgerSender(bytes_message),
             NameSpace::HbAvss => NodeMessage::HbAvssSender(bytes_message),
-            NameSpace::Broadcast => NodeMessage::BroadcastSender(bytes_message),
+            NameSpace::Feldman => NodeMessage::FeldmanSender(sender, bytes_message),
+            NameSpace::NiPvss => NodeMessage::NiPvssSender(bytes_message),
+            NameSpace::Aba => NodeMessage::AbaSender(sender, bytes_message),
+            NameSpace::Acs => panic!("ACS has no message of its own"),
+            NameSpace::Sign => NodeMessage::SignSender(sender, bytes_message),
+            NameSpace::Decrypt => NodeMessage::DecryptSender(sender, bytes_message),
+            NameSpace::Reshare => NodeMessage::ReshareSender(sender, bytes_message),
+            NameSpace::Triples => NodeMessage::TriplesSender(sender, bytes_message),
+            NameSpace::Broadcast => NodeMessage::BroadcastSender(sender, bytes_message),
             NameSpace::SecureMsgDis => NodeMessage::SMDSender(bytes_message),
             NameSpace::OneSidedVote => NodeMessage::OneSidedVoteSender(bytes_message),
             NameSpace::DisperseRetrieve => NodeMessage::DispRetSender(bytes_message),
@@ -211,13 +490,27 @@ impl Node {
         }
     }
 
+    /// Forwards the messages to the listeners of their namespaces, when only is set the messages of
+    /// the other namespaces are dropped
     pub async fn listen_at(
         node: Wrapped<Node>,
         mut receiver: tokio::sync::mpsc::Receiver<Message>,
+        only: Option<Vec<NameSpace>>,
     ) {
-        while let Some(mut bytes_message) = receiver.recv().await {
-            let msg =
-                Self::node_message_from_namespace(bytes_message.remove(0).into(), bytes_message);
+        while let Some((sender, mut bytes_message)) = receiver.recv().await {
+            let namespace = match bytes_message
+                .first()
+                .copied()
+                .and_then(NameSpace::from_checked)
+            {
+                Some(NameSpace::Acs | NameSpace::Heart) | None => continue,
+                Some(namespace) => namespace,
+            };
+            if only.as_ref().is_some_and(|only| !only.contains(&namespace)) {
+                continue;
+            }
+            bytes_message.remove(0);
+            let msg = Self::node_message_from_namespace(namespace, sender, bytes_message);
             Self::wait_and_send(&node, msg).await;
         }
         log!(node, "Exiting listen_at");
@@ -294,6 +587,14 @@ impl Node {
                 NameSpace::LightWeight => lightweight_listen(node).await,
                 NameSpace::Badger => badger_listen(node).await,
                 NameSpace::HbAvss => hbavss_listen(node).await,
+                NameSpace::Feldman => feldman_listen(node).await,
+                NameSpace::NiPvss => ni_pvss_listen(node).await,
+                NameSpace::Aba => aba_listen(node).await,
+                NameSpace::Acs => acs_listen(node).await,
+                NameSpace::Sign => panic!("The sign step has no listener"),
+                NameSpace::Decrypt => panic!("The decrypt step has no listener"),
+                NameSpace::Reshare => panic!("The reshare step has no listener"),
+                NameSpace::Triples => panic!("The triples step has no listener"),
                 NameSpace::OneSidedVote => one_sided_vote_listen(node).await,
                 NameSpace::DisperseRetrieve => disperse_retrieve_listener(node).await,
                 NameSpace::Heart => panic!("Private namespace"),
@@ -308,14 +609,31 @@ impl Node {
     ) {
         let mut to_init = algo.get_subprotocols();
         to_init.push(NameSpace::from(algo));
-        for namespace in to_init {
+        Self::start_listeners(node, to_init, message_receiver, false).await
+    }
+
+    /// Starts the listeners of the namespaces, if exclusive the messages of the other ones are
+    /// dropped instead of waiting for a listener started later by the step
+    async fn start_listeners(
+        node: Wrapped<Node>,
+        to_init: Vec<NameSpace>,
+        message_receiver: tokio::sync::mpsc::Receiver<Message>,
+        exclusive: bool,
+    ) {
+        let only = exclusive.then(|| to_init.clone());
+        Self::start_subprotocols(&node, to_init).await;
+        spawn(async move {
+            Self::listen_at(node, message_receiver, only).await;
+        });
+    }
+
+    /// Starts the listeners of the namespaces, the messages are already forwarded by listen_at
+    pub async fn start_subprotocols(node: &Wrapped<Node>, namespaces: Vec<NameSpace>) {
+        for namespace in namespaces {
             let handler = Self::start_listener_from_namespace(node.clone(), namespace).await;
             node.lock().await.push_handler(handler);
-            Self::wait_for_channel(&node, namespace_to_channel_id(namespace)).await;
+            Self::wait_for_channel(node, namespace_to_channel_id(namespace)).await;
         }
-        spawn(async move {
-            Self::listen_at(node, message_receiver).await;
-        });
     }
 
     pub fn kill_channel(&mut self, channel: ChannelId) {
@@ -357,16 +675,15 @@ impl Node {
 
     pub async fn save_share(&mut self, share: Share) {
         self.set_mut().new_share(share);
-        if self.channel_is_setup(NodeMessage::ShareReceivedConst) {
-            if self
+        if self.channel_is_setup(NodeMessage::ShareReceivedConst)
+            && self
                 .send_message(NodeMessage::ShareReceived)
                 .await
                 .wait(None)
                 .await
                 .is_err()
-            {
-                panic!()
-            }
+        {
+            panic!()
         }
     }
 
@@ -385,13 +702,12 @@ impl Node {
         self.keypair.sign(&enc!(self.get_comm()))
     }
 
+    pub fn sign_bytes(&self, bytes: &[u8]) -> Sign {
+        self.keypair.sign(bytes)
+    }
+
     pub fn shares_vec(&self) -> Vec<Share> {
-        let mut shares = self
-            .set()
-            .set()
-            .iter()
-            .map(|(_, s)| s.clone())
-            .collect::<Vec<_>>();
+        let mut shares = self.set().set().values().cloned().collect::<Vec<_>>();
         shares.sort();
         shares
     }
@@ -412,10 +728,28 @@ impl Node {
         self.set().get(self.index())
     }
 
+    /// Shares of every evaluation point held by the node
+    pub fn my_shares(&self) -> Vec<Share> {
+        self.config
+            .points(self.index())
+            .map(|i| self.set().get(i).clone())
+            .collect()
+    }
+
     pub fn set(&self) -> &CryptoSet {
         &self.shares
     }
 
+    pub fn coin_set(&self) -> &CryptoSet {
+        self.coin_set
+            .as_ref()
+            .expect("No key to flip the coins of the binary agreements")
+    }
+
+    pub fn record_rounds(&mut self, rounds: u16) {
+        self.rounds = Some(self.rounds.map_or(rounds, |r| r.max(rounds)))
+    }
+
     pub fn set_mut(&mut self) -> &mut CryptoSet {
         &mut self.shares
     }
@@ -434,11 +768,26 @@ impl Node {
 
     pub fn output(node: Wrapped<Self>) {
         spawn(async move {
+            {
+                let mut node = node.lock().await;
+                if node.im_done {
+                    return;
+                }
+                if node.step() == Step::Beacon && !node.sharing_over && !node.crashed {
+                    node.sharing_over = true;
+                    node.vote_for_session();
+                    node.send_message(NodeMessage::BeaconSharingOver).await;
+                    return;
+                }
+            }
             log!(node, "Outputing !");
             let (result, set, handlers) = {
                 let mut node = node.lock().await;
                 // assert!(node.has_share() && node.has_comm());
                 node.im_done = true;
+                if !node.crashed && node.step() != Step::Beacon {
+                    node.vote_for_session();
+                }
                 let handlers = node
                     .handlers
                     .take()
@@ -447,8 +796,11 @@ impl Node {
                     .collect::<Vec<Handler>>();
                 let result = node.get_result();
                 let set = match node.step() {
-                    Step::Sharing => Some(node.shares.extract()),
-                    Step::Reconstruct => None,
+                    _ if node.crashed => None,
+                    Step::Sharing | Step::Refresh => Some(node.shares.extract()),
+                    Step::Reshare => node.shares.has_comm().then(|| node.shares.extract()),
+                    Step::Triples => Some(node.shares.extract()),
+                    Step::Reconstruct | Step::Beacon | Step::Sign | Step::Decrypt => None,
                 };
                 node.senders.shutdown_all_clone();
                 (result, set, handlers)
@@ -461,8 +813,11 @@ impl Node {
             let output = {
                 let mut node = node.lock().await;
                 let summaries = node.summaries.take().unwrap();
-                let output = (node.op_id(), NodeProcessOutput::new(result, summaries, set));
-                output.1
+                let mut output = NodeProcessOutput::new(result, summaries, set);
+                output.private_share = node.private_share.take();
+                output.rounds = node.rounds;
+                output.crashed = node.crashed;
+                output
             };
             log!(node, "Sending output notif");
             node.lock()
@@ -471,6 +826,7 @@ impl Node {
                 .output(output)
                 .await
                 .unwrap();
+            node.lock().await.crash_notify.notify_one();
             log!(
                 node,
                 "Successfully sent output notif, {}",
@@ -479,6 +835,59 @@ impl Node {
         });
     }
 
+    /// Votes for our session in the agreement on the sessions, once it is over on our side
+    fn vote_for_session(&self) {
+        if let (Some(link), Some(session)) = (&self.link, self.session) {
+            let _ = link.over.try_send(session as u16);
+        }
+    }
+
+    /// Waits for the sessions agreed on, none if the sessions are not agreed on
+    pub async fn agreed_sessions(node: &Wrapped<Self>) -> Option<Vec<u16>> {
+        let mut agreed = node.lock().await.link.as_ref()?.agreed.clone();
+        let agreed = agreed.wait_for(Option::is_some).await.ok()?;
+        agreed.clone()
+    }
+
+    /// Gives up the operation, the node then reports no output as if it crashed
+    pub fn abort(node: Wrapped<Self>) {
+        spawn(async move {
+            node.lock().await.crashed = true;
+            Self::output(node)
+        });
+    }
+
+    /// Ends the operation once the node crashes, it then reports the crash instead of an output
+    async fn end_on_crash(node: Wrapped<Self>) {
+        let notify = node.lock().await.crash_notify.clone();
+        notify.notified().await;
+        Self::output(node)
+    }
+
+    /// Number of the nb messages about to be sent which leave before the node crashes
+    fn before_crash(&mut self, msg: &[u8], nb: usize) -> usize {
+        let sent = match self.config.crash_point() {
+            _ if self.crashed => 0,
+            Some(CrashPoint::Messages(k)) => nb.min(k as usize - self.nb_sent),
+            Some(CrashPoint::Phase(phase)) if NameSpace::from(msg[0]) == phase => 0,
+            _ => nb,
+        };
+        self.nb_sent += sent;
+        let reached = matches!(
+            self.config.crash_point(),
+            Some(CrashPoint::Messages(k)) if self.nb_sent == k as usize
+        );
+        if sent < nb || reached {
+            self.crashed = true;
+            self.crash_notify.notify_one();
+        }
+        sent
+    }
+
+    pub fn set_private_share(&mut self, shares: Vec<u8>) {
+        self.private_share = Some(shares)
+    }
+
     pub fn get_all_pkey(&self) -> Arc<Vec<PublicKey>> {
         self.public_keys.clone()
     }
@@ -519,10 +928,6 @@ impl Node {
         self.config.l()
     }
 
-    pub fn dealer_corruption(&self) -> u16 {
-        self.config().dealer_corruption()
-    }
-
     pub fn batch_size(&self) -> usize {
         self.config().batch_size()
     }
@@ -531,14 +936,49 @@ impl Node {
         self.config.is_byz()
     }
 
+    /// Index of the concurrent sharing of the node, 0 when the operation has a single one
+    pub fn session(&self) -> u8 {
+        self.session.unwrap_or(0)
+    }
+
+    fn is_silent(&self) -> bool {
+        self.config.byz_behaviour() == Some(ByzBehaviour::Silent)
+    }
+
+    /// Whether the message sent to the node i is replaced according to the byzantine behaviour
+    fn disguises_for(&self, i: usize) -> bool {
+        match self.config.byz_behaviour() {
+            Some(ByzBehaviour::Garbage) => true,
+            Some(ByzBehaviour::Equivocating) => i % 2 == 1,
+            _ => false,
+        }
+    }
+
+    /// Garbage in place of the message, or a well formed message conflicting with it when the node
+    /// equivocates
+    fn disguise(&self, msg: &[u8]) -> Vec<u8> {
+        match self.config.byz_behaviour() {
+            Some(ByzBehaviour::Equivocating) => conflicting(msg),
+            _ => garble(msg),
+        }
+    }
+
     pub fn is_dealer_corrupted(&self) -> bool {
-        self.config().dealer_corruption() > 0
+        self.config().is_dealer_corrupted()
     }
 
     pub fn config(&self) -> &Configuration {
         &self.config
     }
 
+    /// Configuration of the nodes running the agreements, the source committee of a resharing
+    pub fn agreement_config(&self) -> &Configuration {
+        match self.config.step() {
+            Step::Reshare => &self.config.committees().source,
+            _ => &self.config,
+        }
+    }
+
     pub fn uindex(&self) -> usize {
         self.index() as usize
     }
@@ -576,14 +1016,38 @@ impl Node {
         }
     }
 
-    pub fn give_contact(&mut self, i: usize, msg: Vec<u8>) {
+    fn tag_session(&self, msg: &mut Vec<u8>) {
+        if let Some(session) = self.session {
+            msg.insert(1, session)
+        }
+    }
+
+    pub fn give_contact(&mut self, i: usize, mut msg: Vec<u8>) {
+        if self.is_silent() || self.before_crash(&msg, 1) == 0 {
+            return;
+        }
+        if self.disguises_for(i) {
+            msg = self.disguise(&msg);
+        }
+        self.tag_session(&mut msg);
         self.summaries.as_mut().unwrap().new_message_sent(i);
         let id = self.op_id();
         let index = self.index();
         self.get_network().give_message(i, msg, id, index);
     }
 
-    pub fn contact(&mut self, i: usize, msg: Arc<Vec<u8>>) {
+    pub fn contact(&mut self, i: usize, mut msg: Arc<Vec<u8>>) {
+        if self.is_silent() || self.before_crash(&msg, 1) == 0 {
+            return;
+        }
+        if self.disguises_for(i) {
+            msg = Arc::new(self.disguise(&msg));
+        }
+        if self.session.is_some() {
+            let mut tagged = (*msg).clone();
+            self.tag_session(&mut tagged);
+            msg = Arc::new(tagged);
+        }
         self.summaries.as_mut().unwrap().new_message_sent(i);
         let id = self.op_id();
         let index = self.index();
@@ -606,7 +1070,10 @@ impl Node {
         let messages = get_disperse_messages(messages, self.n() as usize, self.t() as usize);
         let id = self.index();
         let mut my_message = Vec::new();
-        for (i, msg) in (0..self.n() - self.dealer_corruption()).zip(messages.into_iter()) {
+        for (i, msg) in (0..self.n()).zip(messages) {
+            if self.config().omits(i) {
+                continue;
+            }
             if i != id {
                 self.contact(i as usize, Arc::new(msg))
             } else {
@@ -614,7 +1081,8 @@ impl Node {
             }
         }
         if !my_message.is_empty() {
-            let msg = Self::node_message_from_namespace(my_message.remove(0).into(), my_message);
+            let msg =
+                Self::node_message_from_namespace(my_message.remove(0).into(), id, my_message);
             self.send_message(msg).await;
         }
     }
@@ -641,7 +1109,10 @@ impl Node {
             get_secure_message_dis_transcripts(messages, self.n() as usize, self.t() as usize + 1);
         let id = self.index();
         let mut my_message = Vec::new();
-        for (i, msg) in (0..self.n() - self.dealer_corruption()).zip(messages.into_iter()) {
+        for (i, msg) in (0..self.n()).zip(messages) {
+            if self.config().omits(i) {
+                continue;
+            }
             if i != id {
                 self.contact(i as usize, Arc::new(msg))
             } else {
@@ -649,7 +1120,8 @@ impl Node {
             }
         }
         if !my_message.is_empty() {
-            let msg = Self::node_message_from_namespace(my_message.remove(0).into(), my_message);
+            let msg =
+                Self::node_message_from_namespace(my_message.remove(0).into(), id, my_message);
             self.send_message(msg).await;
         }
     }
@@ -672,41 +1144,126 @@ impl Node {
             .await
     }
 
+    /// Broadcast to the nodes of the agreement configuration only
+    pub async fn broadcast_to_voters(&mut self, msg: Vec<u8>, with_me: bool) {
+        let n = self.agreement_config().n() as usize;
+        let to_contact = (0..n).filter(|i| *i != self.uindex()).collect();
+        self.broadcast_specific_network_part(msg, with_me, to_contact)
+            .await
+    }
+
     async fn broadcast_specific_network_part(
         &mut self,
         mut msg: Vec<u8>,
         with_me: bool,
-        to_contact: Vec<usize>,
+        mut to_contact: Vec<usize>,
     ) {
         let id = self.op_id();
         let index = self.index();
 
-        for i in &to_contact {
-            self.summaries.as_mut().unwrap().new_message_sent(*i)
-        }
+        if !self.is_silent() {
+            let sent = self.before_crash(&msg, to_contact.len());
+            to_contact.truncate(sent);
+            for i in &to_contact {
+                self.summaries.as_mut().unwrap().new_message_sent(*i)
+            }
 
-        self.get_network_mut()
-            .broadcast(msg.clone(), id, Some(to_contact), index);
-        if with_me {
-            let msg = Self::node_message_from_namespace(msg.remove(0).into(), msg);
+            let (disguised, to_contact): (Vec<usize>, Vec<usize>) =
+                to_contact.into_iter().partition(|i| self.disguises_for(*i));
+            if !disguised.is_empty() {
+                let mut other = self.disguise(&msg);
+                self.tag_session(&mut other);
+                self.get_network_mut()
+                    .broadcast(other, id, Some(disguised), index);
+            }
+            let mut tagged = msg.clone();
+            self.tag_session(&mut tagged);
+            self.get_network_mut()
+                .broadcast(tagged, id, Some(to_contact), index);
+        }
+        if with_me && !self.crashed {
+            let msg = Self::node_message_from_namespace(msg.remove(0).into(), index, msg);
             self.send_message(msg).await;
         }
     }
 
-    pub async fn reliable_broadcast(&mut self, kind: BroadcastMessageType, mut message: Vec<u8>) {
+    pub async fn reliable_broadcast(&mut self, kind: BroadcastMessageType, message: Vec<u8>) {
+        let to_contact = self.not_omitted().collect();
+        let proposal = self.proposal(kind, message);
+        self.broadcast_specific_network_part(proposal, false, to_contact)
+            .await;
+    }
+
+    /// Reliable broadcast where the nodes the dealer equivocates with are proposed other
+    pub async fn equivocating_broadcast(
+        &mut self,
+        kind: BroadcastMessageType,
+        message: Vec<u8>,
+        other: Vec<u8>,
+    ) {
+        let (misled, to_contact): (Vec<usize>, Vec<usize>) = self
+            .not_omitted()
+            .partition(|i| self.config().equivocates_with(*i as u16));
+        let proposal = self.proposal(kind, message);
+        self.broadcast_specific_network_part(proposal, false, to_contact)
+            .await;
+        let proposal = self.proposal(kind, other);
+        self.broadcast_specific_network_part(proposal, false, misled)
+            .await;
+    }
+
+    fn not_omitted(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.n())
+            .filter(|i| !self.config().omits(*i))
+            .map(|i| i as usize)
+    }
+
+    fn proposal(&self, kind: BroadcastMessageType, mut message: Vec<u8>) -> Vec<u8> {
         let mut b_message = vec![
             NameSpace::Broadcast.into(),
             BroadcastCommand::Propose.into(),
             kind.into(),
         ];
         b_message.append(&mut message);
-        let to_contact: Vec<usize> =
-            (0..(self.n() - self.dealer_corruption()) as usize).collect::<Vec<usize>>();
-        self.broadcast_specific_network_part(b_message, false, to_contact)
-            .await;
+        b_message
     }
 
     pub async fn encrypt_message(&self, msg: Vec<u8>, pkey: NodeId) -> Vec<u8> {
         enc!(self.get_specific_key(pkey).blstt_encrypt(msg))
     }
 }
+
+/// Random bytes in place of the content of a message, its namespace and command are kept so that it
+/// still reaches the protocol
+fn garble(msg: &[u8]) -> Vec<u8> {
+    let mut rng = thread_rng();
+    msg.iter()
+        .enumerate()
+        .map(|(i, b)| if i < 2 { *b } else { rng.gen() })
+        .collect()
+}
+
+/// A well formed message conflicting with msg: the echoes and readies of a reliable broadcast vouch
+/// for another message and the votes of an ABA are flipped, the other messages are kept
+fn conflicting(msg: &[u8]) -> Vec<u8> {
+    match NameSpace::from(msg[0]) {
+        NameSpace::Broadcast => match BroadcastCommand::from(msg[1]) {
+            command @ (BroadcastCommand::Echo | BroadcastCommand::Ready) => {
+                let mut tr: Transcript = dec!(msg[2..]);
+                tr.hash.iter_mut().for_each(|b| *b = !*b);
+                tr.share.iter_mut().for_each(|b| *b = !*b);
+                enc!(Broadcast, command, tr)
+            }
+            BroadcastCommand::Propose => msg.to_vec(),
+        },
+        NameSpace::Aba => match AbaCommand::from(msg[1]) {
+            AbaCommand::BVal | AbaCommand::Aux | AbaCommand::Term => {
+                let mut msg = msg.to_vec();
+                *msg.last_mut().unwrap() ^= 1;
+                msg
+            }
+            AbaCommand::Coin => msg.to_vec(),
+        },
+        _ => msg.to_vec(),
+    }
+}
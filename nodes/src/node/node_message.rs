@@ -1,6 +1,6 @@
 use crate::disperse_retrieve::messages::{Echo, Propose, Ready};
 use crate::{
-    crypto::{Share, Sign},
+    crypto::{Commitment, RandComm, Share, Sign},
     haven::crypto::{EchoMessage, SendMessage},
     hbavss::{HbAvssAssist, HbAvssComplaint},
     secure_message_dist::{ForwardMessage, ForwardTag, SmdMemory},
@@ -17,21 +17,27 @@ type WrappedSmdMem = Wrapped<SmdMemory>;
 pub enum NodeMessage {
     ShareReceived,
     BeaconSender(Bytes),
+    BeaconSharingOver,
     SMDSender(Bytes),
     SMDForwardRequest(ForwardTag),
     SMDForwardLightWeightComplaint(WrappedSmdMem, ForwardMessage),
     SMDForwardLightWeightAssist(WrappedSmdMem, ForwardMessage),
     SMDForwardLightWeightReport(WrappedSmdMem, ForwardMessage),
     SMDOutput(Bytes),
-    BroadcastSender(Bytes),
+    BroadcastSender(u16, Bytes), // (sender, message)
     BroadcastAvssSimpl(Bytes),
     BroadcastBingo(Bytes),
     BroadcastLightWeight(Bytes),
     BroadcastBadger(Bytes),
     BroadcastHbAvss(Bytes),
+    BroadcastFeldman(Bytes),
+    BroadcastNiPvss(Bytes),
+    BroadcastAcs(u16, Bytes),
+    BroadcastReshare(u16, Bytes),
+    BroadcastTriples(u16, Bytes),
     AvssSimplSender(Bytes),
     AvssSimplDealerMessage(u16, Sign),
-    AvssSimplExtShare(Share),
+    AvssSimplExtShare(Share, Option<Vec<RandComm>>),
     BingoSender(Bytes),
     BingoRow(Share),
     BingoCol(Share),
@@ -40,6 +46,7 @@ pub enum NodeMessage {
     BingoReconstructShare(Share),
     LightWeightSender(Bytes),
     LightWeightEndOfProcessing,
+    LightWeightReconstructShare(Share),
     OneSidedVoteSender(Bytes),
     OneSidedVoteBroadcastVoteRequest,
     OneSidedVoteBroadcastOkRequest,
@@ -59,10 +66,31 @@ pub enum NodeMessage {
     HbAvssEndOfProcessing,
     HbAvssComplaint(HbAvssComplaint),
     HbAvssAssist(HbAvssAssist),
-    HavenSender(Bytes),
+    HbAvssReconstructShare(Share),
+    HavenSender(u16, Bytes), // (sender, message)
     HavenSend(SendMessage),
     HavenEcho(EchoMessage),
     HavenReady(Vec<u8>),
+    HavenReconstructShare(Share),
+    HavenCommRequest(u16),
+    HavenComm(u16, Commitment), // (sender, commitment)
+    FeldmanSender(u16, Bytes),  // (sender, message)
+    FeldmanShare(Vec<Share>),
+    FeldmanEcho(u16),
+    FeldmanComplaint(u16),
+    FeldmanReveal(Vec<Share>),
+    FeldmanReconstructShare(Vec<Share>),
+    NiPvssSender(Bytes),
+    NiPvssReconstructShare(Share, Vec<RandComm>),
+    AbaSender(u16, Bytes),     // (sender, message)
+    AbaInput(u16, bool),       // (instance, estimate)
+    AbaOutput(u16, bool, u16), // (instance, decision, round)
+    AcsInput(Bytes),
+    AcsOutput(Vec<(u16, Bytes)>),
+    SignSender(u16, Bytes),    // (sender, message)
+    DecryptSender(u16, Bytes), // (sender, message)
+    ReshareSender(u16, Bytes), // (sender, message)
+    TriplesSender(u16, Bytes), // (sender, message)
     Close,
 }
 
@@ -82,6 +110,14 @@ pub fn namespace_to_channel_id(namespace: NameSpace) -> ChannelId {
         NameSpace::LightWeight => NodeMessage::LightWeightSenderConst,
         NameSpace::Badger => NodeMessage::BadgerSenderConst,
         NameSpace::HbAvss => NodeMessage::HbAvssSenderConst,
+        NameSpace::Feldman => NodeMessage::FeldmanSenderConst,
+        NameSpace::NiPvss => NodeMessage::NiPvssSenderConst,
+        NameSpace::Aba => NodeMessage::AbaSenderConst,
+        NameSpace::Acs => NodeMessage::AcsInputConst,
+        NameSpace::Sign => NodeMessage::SignSenderConst,
+        NameSpace::Decrypt => NodeMessage::DecryptSenderConst,
+        NameSpace::Reshare => NodeMessage::ReshareSenderConst,
+        NameSpace::Triples => NodeMessage::TriplesSenderConst,
         NameSpace::OneSidedVote => NodeMessage::OneSidedVoteSenderConst,
         NameSpace::DisperseRetrieve => NodeMessage::DispRetSenderConst,
         NameSpace::Heart => panic!("Private namespace"),
@@ -18,6 +18,8 @@ pub struct NodeProcessInput {
     pub keypair: KeyPair,
     pub dealer: u16,
     pub base: Base,
+    pub session: Option<u8>, // Index of the dealer's session when several nodes deal at once
+    pub coin_set: Option<CryptoSet>, // Key shares flipping the coins of the binary agreements
 }
 
 impl NodeProcessInput {
@@ -32,6 +34,8 @@ impl NodeProcessInput {
         shares: CryptoSet,
         dealer: u16,
         base: Base,
+        session: Option<u8>,
+        coin_set: Option<CryptoSet>,
     ) -> Self {
         NodeProcessInput {
             index,
@@ -43,6 +47,8 @@ impl NodeProcessInput {
             dealer,
             keypair,
             base,
+            session,
+            coin_set,
         }
     }
 }
@@ -1,11 +1,18 @@
 use global_lib::config_treatment::result_fields::ResultDuration;
 
-use crate::{crypto::crypto_set::CryptoSet, system::summaries::Summaries};
+use crate::{
+    crypto::crypto_set::{CryptoSet, CryptoSetIdentity},
+    system::summaries::Summaries,
+};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct NodeProcessOutput {
     pub result: ResultDuration,
-    pub share_set: Option<CryptoSet>,
+    pub share_sets: Vec<CryptoSet>,
+    pub refresh: Option<CryptoSet>, // Sum of the zero sharings to add to a stored set
+    pub private_share: Option<Vec<u8>>, // Shares for the interface in a private reconstruction
+    pub crashed: bool,              // The node crashed before the end of the operation
+    pub rounds: Option<u16>,        // Most rounds taken by a binary agreement of the operation
     pub summaries: Summaries,
 }
 
@@ -14,7 +21,47 @@ impl NodeProcessOutput {
         Self {
             result,
             summaries,
-            share_set,
+            share_sets: share_set.into_iter().collect(),
+            refresh: None,
+            private_share: None,
+            crashed: false,
+            rounds: None,
         }
     }
+
+    /// Merges the outputs of the concurrent sessions of a same operation
+    pub fn merge(outputs: Vec<Self>) -> Self {
+        let mut outputs = outputs.into_iter();
+        let mut res = outputs.next().expect("No session to merge");
+        for output in outputs {
+            res.result = res.result.max(output.result);
+            res.summaries += output.summaries;
+            res.crashed |= output.crashed;
+            res.rounds = res.rounds.max(output.rounds);
+            res.share_sets.extend(output.share_sets);
+        }
+        res
+    }
+
+    /// Sums the zero sharings of the sessions of a refresh into the update of the set `identity`
+    pub fn into_refresh(mut self, identity: CryptoSetIdentity) -> Self {
+        self.refresh = Some(self.sum_sets(identity));
+        self
+    }
+
+    /// Sums the sharings of the sessions of a key generation into the key set `identity`
+    pub fn into_key(mut self, identity: CryptoSetIdentity) -> Self {
+        self.share_sets = vec![self.sum_sets(identity)];
+        self
+    }
+
+    fn sum_sets(&mut self, identity: CryptoSetIdentity) -> CryptoSet {
+        let mut sets = std::mem::take(&mut self.share_sets).into_iter();
+        let mut sum = sets.next().expect("No sharing to sum");
+        for set in sets {
+            sum += &set;
+        }
+        sum.set_identity(identity);
+        sum
+    }
 }
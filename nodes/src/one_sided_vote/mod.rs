@@ -30,8 +30,12 @@ pub async fn one_sided_vote_listen(node: Wrapped<Node>) {
             continue;
         }
         match msg {
-            NodeMessage::OneSidedVoteSender(msg) => match OneSidedVoteCommand::from(msg[0]) {
-                OneSidedVoteCommand::Ok => {
+            NodeMessage::OneSidedVoteSender(msg) => match msg
+                .first()
+                .copied()
+                .and_then(OneSidedVoteCommand::from_checked)
+            {
+                Some(OneSidedVoteCommand::Ok) => {
                     if has_voted {
                         continue;
                     }
@@ -41,7 +45,7 @@ pub async fn one_sided_vote_listen(node: Wrapped<Node>) {
                         has_voted = true;
                     }
                 }
-                OneSidedVoteCommand::Vote => {
+                Some(OneSidedVoteCommand::Vote) => {
                     vote_count += 1;
                     if !has_voted && vote_count > t {
                         broadcast_vote(&node).await;
@@ -53,6 +57,7 @@ pub async fn one_sided_vote_listen(node: Wrapped<Node>) {
                         done = true;
                     }
                 }
+                None => continue,
             },
             NodeMessage::OneSidedVoteBroadcastVoteRequest => {
                 if !has_voted && vote_count > t {
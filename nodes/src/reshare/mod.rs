@@ -0,0 +1,273 @@
+use crate::{
+    acs::agree_on,
+    break_if_over,
+    broadcast::broadcast_message_types::BroadcastMessageType,
+    crypto::{
+        combine_reshare, compute_feldman_comm_and_shares_of, crypto_set::CryptoSet,
+        encrypt_sub_shares, feldman_verify, verify_reshare_dealing, Commitment, EncSubShares,
+        Share, SubShareComplaint,
+    },
+    log,
+    node::{configuration::Committees, node::Node, node_message::NodeMessage},
+    sub_shares::{SubShares, SubSharesAction},
+};
+use global_lib::{
+    enc,
+    messages::{NameSpace, ReshareCommand},
+    try_dec, Wrapped,
+};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    sync::Arc,
+};
+use tokio::sync::mpsc::{channel, Receiver as VoteReceiver, Sender as VoteSender};
+
+type Agreed = (Vec<u16>, Commitment); // (dealers kept, commitment of the source committee)
+type Dealing = (Commitment, EncSubShares);
+
+/// Dynamic resharing: every node of the source committee deals a Feldman sharing of its share to
+/// the target committee, the sub-shares being encrypted in the broadcast dealing. A target node
+/// acknowledges the dealings whose sub-share is valid to the source committee, and complains with
+/// a proof on the others. The source committee agrees through an ACS on the dealings that match
+/// its commitment and that n - t target nodes acknowledged, and each of its nodes sends the t + 1
+/// first agreed ones with the source commitment to the target nodes. A target node keeps them once
+/// t + 1 source nodes sent the same, recovers its sub-shares of the faulty dealers from the other
+/// target nodes, then combines the dealings into shares of the same secret.
+pub async fn reshare(node: Wrapped<Node>) {
+    let channels = [
+        NodeMessage::ReshareSenderConst,
+        NodeMessage::BroadcastReshareConst,
+    ];
+    let receiver = node.lock().await.subscribe_multiple(&channels);
+    let committees = node.lock().await.config().committees().clone();
+    let in_source = committees.in_source(node.lock().await.index());
+    let votes = match in_source {
+        true => {
+            Node::start_subprotocols(&node, vec![NameSpace::Aba, NameSpace::Acs]).await;
+            let (votes, agreement) = channel(committees.source.n() as usize);
+            tokio::spawn(vouch(node.clone(), agreement));
+            Some(votes)
+        }
+        false => None,
+    };
+    let cloned_node = node.clone();
+    tokio::spawn(async move { reshare_receiver(cloned_node, receiver, votes).await });
+    let mut node = node.lock().await;
+    if node.is_byz() || !in_source {
+        return;
+    }
+    let secrets = node.my_share().only_share().batch().clone();
+    let (comm, sub_shares) = compute_feldman_comm_and_shares_of(&committees.target, &secrets);
+    let pkeys = (0..committees.target.n())
+        .map(|i| node.get_specific_key(committees.offset + i).c_key())
+        .collect::<Vec<_>>();
+    let enc = encrypt_sub_shares(&committees.target, &pkeys, &sub_shares);
+    let dealing: Dealing = (comm, enc);
+    node.reliable_broadcast(BroadcastMessageType::Reshare, enc!(dealing))
+        .await;
+}
+
+/// Sends the dealings agreed by the source committee to the target nodes, along with the source
+/// commitment they match
+async fn vouch(node: Wrapped<Node>, votes: VoteReceiver<u16>) {
+    let mut set = match agree_on(&node, votes).await {
+        Some(set) => set,
+        None => return,
+    };
+    log!(node, "RESHARE: agreed on the dealings of {set:?}");
+    let mut node_guard = node.lock().await;
+    let committees = node_guard.config().committees().clone();
+    set.truncate(committees.source.get_threshold());
+    let agreed: Agreed = (set, node_guard.get_comm().clone());
+    let msg = Arc::new(enc!(Reshare, ReshareCommand::Agreed, agreed));
+    for i in 0..committees.target.n() {
+        node_guard.contact((committees.offset + i) as usize, msg.clone());
+    }
+    if !committees.in_target(node_guard.index()) {
+        node_guard.set_mut().clear();
+        drop(node_guard);
+        Node::output(node);
+    }
+}
+
+async fn reshare_receiver(
+    node: Wrapped<Node>,
+    mut receiver: Receiver<NodeMessage>,
+    votes: Option<VoteSender<u16>>,
+) {
+    let (committees, index, own_comm, mut sub_shares) = {
+        let node = node.lock().await;
+        let committees = node.config().committees().clone();
+        let own_comm = votes.as_ref().map(|_| node.get_comm().clone());
+        let target = &committees.target;
+        let pkeys = (0..target.n())
+            .map(|i| node.get_specific_key(committees.offset + i).c_key())
+            .collect();
+        let sub_shares = SubShares::new(
+            target.clone(),
+            target.index(),
+            *node.my_decrypt_skey(),
+            pkeys,
+        );
+        (committees, node.index(), own_comm, sub_shares)
+    };
+    let (source, target, offset) = (&committees.source, &committees.target, committees.offset);
+    let in_target = committees.in_target(index);
+    let enough_acks = (target.n() - target.t()) as usize;
+    let mut dealings = HashMap::<u16, Commitment>::new();
+    let mut valid = HashSet::<u16>::new(); // Dealings matching the commitment of the source node
+    let mut acks = HashMap::<u16, HashSet<u16>>::new();
+    let mut voted = HashSet::<u16>::new();
+    let mut vouches = HashMap::<u16, Agreed>::new();
+    let mut agreed: Option<Agreed> = None;
+    loop {
+        let msg = break_if_over!(receiver);
+        let mut actions = Vec::new();
+        match msg {
+            NodeMessage::ReshareSender(sender, bytes) => {
+                let command = bytes
+                    .first()
+                    .copied()
+                    .and_then(ReshareCommand::from_checked);
+                match command {
+                    Some(ReshareCommand::Agreed)
+                        if agreed.is_none() && committees.in_source(sender) =>
+                    {
+                        let vouch: Agreed = try_dec!(bytes[1..], continue);
+                        let vouch = vouches.entry(sender).or_insert(vouch).clone();
+                        let count = vouches.values().filter(|v| **v == vouch).count();
+                        if count > source.t() as usize {
+                            agreed = Some(vouch);
+                        }
+                    }
+                    _ if !committees.in_target(sender) => continue,
+                    Some(ReshareCommand::Ack) => {
+                        let dealer: u16 = try_dec!(bytes[1..], continue);
+                        acks.entry(dealer).or_default().insert(sender - offset);
+                    }
+                    Some(ReshareCommand::Complaint) if in_target => {
+                        let complaint: SubShareComplaint = try_dec!(bytes[1..], continue);
+                        actions.extend(sub_shares.new_complaint(sender - offset, complaint));
+                    }
+                    Some(ReshareCommand::Release) if in_target => {
+                        let (dealer, share): (u16, Share) = try_dec!(bytes[1..], continue);
+                        if sub_shares.new_release(sender - offset, dealer, share) {
+                            log!(node, "RESHARE: recovered the sub-share of {dealer}");
+                        }
+                    }
+                    _ => continue,
+                }
+            }
+            NodeMessage::BroadcastReshare(i, bytes) => {
+                if !committees.in_source(i) || dealings.contains_key(&i) {
+                    continue;
+                }
+                let (dealing, enc): Dealing = try_dec!(bytes, continue);
+                if let Some(comm) = &own_comm {
+                    if verify_reshare_dealing(source, target, comm, i, &dealing) {
+                        valid.insert(i);
+                    } else {
+                        log!(node, "RESHARE: invalid dealing from {i}");
+                    }
+                }
+                if in_target {
+                    actions = sub_shares.new_dealing(i, dealing.clone(), enc);
+                }
+                dealings.insert(i, dealing);
+            }
+            _ => panic!("Unexpected message"),
+        }
+        send_actions(&node, &committees, actions).await;
+        // A dealing is voted once enough target nodes got a valid sub-share from it, so that the
+        // others can recover theirs
+        if let Some(votes) = &votes {
+            let ready = valid
+                .iter()
+                .filter(|i| {
+                    !voted.contains(*i) && acks.get(*i).map_or(0, |a| a.len()) >= enough_acks
+                })
+                .copied()
+                .collect::<Vec<_>>();
+            for i in ready {
+                let _ = votes.send(i).await;
+                voted.insert(i);
+            }
+        }
+        let (set, comm) = match &agreed {
+            Some(agreed) if in_target => agreed,
+            _ => continue,
+        };
+        if !set
+            .iter()
+            .all(|i| dealings.contains_key(i) && sub_shares.get(*i).is_some())
+        {
+            continue;
+        }
+        let mut node_guard = node.lock().await;
+        // The source nodes voted for the dealings matching their commitment, which the t + 1 ones
+        // vouching for the set share with the honest ones
+        let invalid = set
+            .iter()
+            .find(|i| !verify_reshare_dealing(source, target, comm, **i, &dealings[i]));
+        if let Some(i) = invalid {
+            node_guard.log(&format!("RESHARE: agreed on the invalid dealing of {i}"));
+            drop(node_guard);
+            Node::abort(node);
+            return;
+        }
+        let (new_comm, share) = combine_reshare(source, target, set, &dealings, sub_shares.all());
+        let kept_secret = new_comm
+            .all()
+            .iter()
+            .zip(comm.all())
+            .all(|(new, old)| new[0] == old[0]);
+        if !feldman_verify(target, &new_comm, &share) || !kept_secret {
+            node_guard.log("RESHARE: the combined share doesn't match the source commitment");
+            drop(node_guard);
+            Node::abort(node);
+            return;
+        }
+        let algo = node_guard.config().algo();
+        let mut set = CryptoSet::new((target.n(), target.t(), algo, 0));
+        set.set_comm(new_comm);
+        set.new_share(share);
+        *node_guard.set_mut() = set;
+        drop(node_guard);
+        Node::output(node);
+        return;
+    }
+}
+
+/// Acknowledges the valid sub-shares to the source committee, complains to the target committee
+/// about the invalid ones and releases the sub-shares of the faulty dealers to the complainers
+async fn send_actions(
+    node: &Wrapped<Node>,
+    committees: &Committees,
+    actions: Vec<SubSharesAction>,
+) {
+    let mut node = node.lock().await;
+    let offset = committees.offset;
+    for action in actions {
+        match action {
+            SubSharesAction::Ack(dealer) => {
+                let msg = Arc::new(enc!(Reshare, ReshareCommand::Ack, dealer));
+                for i in 0..committees.source.n() {
+                    node.contact(i as usize, msg.clone());
+                }
+            }
+            SubSharesAction::Complain(complaint) => {
+                node.log(&format!("RESHARE: invalid sub-share from {}", complaint.0));
+                let msg = Arc::new(enc!(Reshare, ReshareCommand::Complaint, *complaint));
+                for i in 0..committees.target.n() {
+                    node.contact((offset + i) as usize, msg.clone());
+                }
+            }
+            SubSharesAction::Release(to, dealer, share) => {
+                let msg = enc!(Reshare, ReshareCommand::Release, (dealer, *share));
+                node.contact((offset + to) as usize, Arc::new(msg));
+            }
+        }
+    }
+}
@@ -45,11 +45,11 @@ pub fn get_secure_message_dis_transcripts(messages: Vec<Bytes>, n: usize, _: usi
         })
         .collect::<Vec<_>>();
 
-    for ((shares, data), key_line) in messages.into_iter().zip(keys.into_iter()) {
+    for ((shares, data), key_line) in messages.into_iter().zip(keys) {
         datas.push(data);
         let enc_shares = shares
             .iter()
-            .zip(key_line.into_iter())
+            .zip(key_line)
             .map(|(s, k)| {
                 let mut s = s.clone();
                 s.append(&mut k.to_bytes_be().to_vec());
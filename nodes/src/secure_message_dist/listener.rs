@@ -6,7 +6,7 @@ use crate::{
     break_if_over,
     node::{node::Node, node_message::NodeMessage},
 };
-use global_lib::{dec, messages::SecureMsgDisCommand, select, wrap, Wrapped};
+use global_lib::{messages::SecureMsgDisCommand, select, try_dec, wrap, Wrapped};
 
 pub async fn listen(node: Wrapped<Node>) {
     let channels = vec![
@@ -38,20 +38,20 @@ pub async fn listen(node: Wrapped<Node>) {
 }
 
 async fn propose(memory: Wrapped<Memory>, bytes: Vec<u8>) {
-    let msg: ProposeMessage = dec!(bytes);
+    let msg: ProposeMessage = try_dec!(bytes);
     memory.lock().await.propose(msg).await;
 }
 async fn new_echo(memory: Wrapped<Memory>, bytes: Vec<u8>) {
-    let msg: EchoMessage = dec!(bytes);
+    let msg: EchoMessage = try_dec!(bytes);
     memory.lock().await.new_echo(msg).await;
 }
 async fn new_vote(memory: Wrapped<Memory>, bytes: Vec<u8>) {
-    let msg: VoteMessage = dec!(bytes);
+    let msg: VoteMessage = try_dec!(bytes);
     memory.lock().await.new_vote(msg).await;
 }
 
 async fn forward_receiv(memory: Wrapped<Memory>, bytes: Vec<u8>) {
-    let msg: ForwardMessage = dec!(bytes);
+    let msg: ForwardMessage = try_dec!(bytes);
     Memory::forward_receiv(memory, msg).await;
 }
 
@@ -0,0 +1,77 @@
+use crate::{
+    break_if_over,
+    crypto::{combine_partial_signs, partial_sign, verify_partial_sign, verify_sign},
+    log,
+    node::{node::Node, node_message::NodeMessage},
+};
+use blstrs::G2Projective;
+use global_lib::{enc, messages::SignCommand, try_dec, Wrapped};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{collections::HashMap, io::Write};
+
+/// Threshold BLS signature: every node signs the message chosen by the interface with its share,
+/// and combines t + 1 partial signatures into one that verifies under the committed secret.
+pub async fn sign(node: Wrapped<Node>) {
+    let cloned_node = node.clone();
+    let mut node = node.lock().await;
+    let receiver = node.subscribe(NodeMessage::SignSenderConst);
+    let mine = partial_sign(node.my_share(), node.config().step_input());
+    let msg = enc!(Sign, SignCommand::PartialSign, mine);
+    tokio::spawn(async move { partial_sign_receiver(cloned_node, receiver, mine).await });
+    if node.is_byz() {
+        return;
+    }
+    node.broadcast(msg, false).await;
+}
+
+async fn partial_sign_receiver(
+    node: Wrapped<Node>,
+    mut receiver: Receiver<NodeMessage>,
+    mine: Vec<G2Projective>,
+) {
+    let (enough, n, index) = {
+        let node = node.lock().await;
+        (node.config().get_threshold(), node.n(), node.index())
+    };
+    let mut partials = HashMap::from([(index, mine)]);
+    loop {
+        let msg = break_if_over!(receiver);
+        if partials.len() >= enough {
+            continue;
+        }
+        let (i, partial): (u16, Vec<G2Projective>) = match msg {
+            NodeMessage::SignSender(sender, bytes) => {
+                match bytes.first().copied().and_then(SignCommand::from_checked) {
+                    Some(SignCommand::PartialSign) => (sender, try_dec!(bytes[1..], continue)),
+                    None => continue,
+                }
+            }
+            _ => panic!("Unexpected message"),
+        };
+        if i >= n || partials.contains_key(&i) {
+            continue;
+        }
+        let valid = {
+            let node = node.lock().await;
+            let config = node.config();
+            let message = config.step_input();
+            verify_partial_sign(config, node.get_comm(), i, message, &partial)
+        };
+        if !valid {
+            log!(node, "Dropped the invalid partial signature of {i}");
+            continue;
+        }
+        partials.insert(i, partial);
+        if partials.len() == enough {
+            {
+                let node = node.lock().await;
+                let config = node.config();
+                let signs = combine_partial_signs(config, &partials);
+                if !verify_sign(config, node.get_comm(), config.step_input(), &signs) {
+                    panic!("FAILED TO COMBINE THE SIGNATURE")
+                }
+            }
+            Node::output(node.clone());
+        }
+    }
+}
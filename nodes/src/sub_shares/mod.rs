@@ -0,0 +1,140 @@
+use crate::{
+    crypto::{
+        decrypt_sub_share, feldman_verify, interpolate_specific_share, sub_share_key,
+        verify_sub_share_complaint, Commitment, EncSubShares, Share, SubShareComplaint,
+    },
+    node::configuration::Configuration,
+};
+use blstrs::{G1Projective, Scalar};
+use std::collections::HashMap;
+
+pub enum SubSharesAction {
+    Ack(u16),                         // The sub-share of the dealer is valid
+    Complain(Box<SubShareComplaint>), // The sub-share of the dealer is invalid
+    Release(u16, u16, Box<Share>),    // (complainer, dealer, sub-share of the node)
+}
+
+// Sub-shares of the dealings encrypted to the nodes of a committee. A node whose sub-share is
+// invalid proves it with the key of its encryption, then the dealer being faulty the other nodes
+// release their sub-shares and the node interpolates its own from them.
+pub struct SubShares {
+    config: Configuration, // Configuration of the committee receiving the sub-shares
+    index: u16,
+    skey: Scalar,
+    pkeys: Vec<G1Projective>, // Keys of the nodes of the committee, by index
+    dealings: HashMap<u16, (Commitment, EncSubShares)>,
+    mine: HashMap<u16, Share>,
+    pending: HashMap<u16, Vec<(u16, SubShareComplaint)>>, // Complaints on dealings not received yet
+    released: HashMap<u16, HashMap<u16, Share>>,
+}
+
+impl SubShares {
+    pub fn new(config: Configuration, index: u16, skey: Scalar, pkeys: Vec<G1Projective>) -> Self {
+        Self {
+            config,
+            index,
+            skey,
+            pkeys,
+            dealings: HashMap::new(),
+            mine: HashMap::new(),
+            pending: HashMap::new(),
+            released: HashMap::new(),
+        }
+    }
+
+    /// The valid sub-share of the node from the dealer, decrypted or recovered
+    pub fn get(&self, dealer: u16) -> Option<&Share> {
+        self.mine.get(&dealer)
+    }
+
+    pub fn all(&self) -> &HashMap<u16, Share> {
+        &self.mine
+    }
+
+    pub fn new_dealing(
+        &mut self,
+        dealer: u16,
+        comm: Commitment,
+        enc: EncSubShares,
+    ) -> Vec<SubSharesAction> {
+        if self.dealings.contains_key(&dealer) {
+            return Vec::new();
+        }
+        let (key, proof) = sub_share_key(&self.config, &enc, &self.skey);
+        let share = decrypt_sub_share(&enc, self.index, &key)
+            .filter(|share| feldman_verify(&self.config, &comm, share));
+        self.dealings.insert(dealer, (comm, enc));
+        let mut actions = vec![match share {
+            Some(share) => {
+                self.mine.insert(dealer, share);
+                SubSharesAction::Ack(dealer)
+            }
+            None => SubSharesAction::Complain(Box::new((dealer, key, proof))),
+        }];
+        for (from, complaint) in self.pending.remove(&dealer).unwrap_or_default() {
+            actions.extend(self.new_complaint(from, complaint));
+        }
+        self.recover(dealer);
+        actions
+    }
+
+    /// Releases the sub-share of the node once the complaint proves that the dealer is faulty
+    pub fn new_complaint(
+        &mut self,
+        from: u16,
+        complaint: SubShareComplaint,
+    ) -> Option<SubSharesAction> {
+        let dealer = complaint.0;
+        let pkey = self.pkeys.get(from as usize)?;
+        let (comm, enc) = match self.dealings.get(&dealer) {
+            Some(dealing) => dealing,
+            None => {
+                self.pending
+                    .entry(dealer)
+                    .or_default()
+                    .push((from, complaint));
+                return None;
+            }
+        };
+        if !verify_sub_share_complaint(&self.config, comm, enc, from, pkey, &complaint) {
+            return None;
+        }
+        let share = self.mine.get(&dealer)?.clone();
+        Some(SubSharesAction::Release(from, dealer, Box::new(share)))
+    }
+
+    /// Returns true once the sub-share of the node is recovered
+    pub fn new_release(&mut self, from: u16, dealer: u16, share: Share) -> bool {
+        if self.mine.contains_key(&dealer) || share.index() != from {
+            return false;
+        }
+        self.released
+            .entry(dealer)
+            .or_default()
+            .entry(from)
+            .or_insert(share);
+        self.recover(dealer)
+    }
+
+    fn recover(&mut self, dealer: u16) -> bool {
+        let (comm, released) = match (self.dealings.get(&dealer), self.released.get(&dealer)) {
+            (Some((comm, _)), Some(released)) if !self.mine.contains_key(&dealer) => {
+                (comm, released)
+            }
+            _ => return false,
+        };
+        let valid = released
+            .values()
+            .filter(|share| feldman_verify(&self.config, comm, share))
+            .take(self.config.get_threshold())
+            .cloned()
+            .collect::<Vec<_>>();
+        if valid.len() < self.config.get_threshold() {
+            return false;
+        }
+        let dom = self.config.get_batch_evaluation_domain();
+        let share = interpolate_specific_share(dom, &valid, self.index as usize);
+        self.mine.insert(dealer, share);
+        true
+    }
+}
@@ -20,13 +20,14 @@ use crate::{
     },
     panic_if_over,
 };
+use blstrs::G1Projective;
 use global_lib::{
     async_private_message,
     config_treatment::fields::Fields,
     dec, enc, explicit_log,
     ip_addr::IpV4,
     log,
-    messages::{InterfaceCode, NameSpace, NodeCommand},
+    messages::{Algo, InterfaceCode, NameSpace, NodeCommand},
     network::Network,
     process_pool::{PoolProcessEnded, ProcessPool},
     select,
@@ -77,7 +78,7 @@ pub struct NodesHeart {
 impl NodesHeart {
     pub async fn new(interface_ip: IpV4, my_ip: IpV4) -> Self {
         let heart = NodesHeart {
-            log: wrap!(File::create(&format!("../logs/node_{my_ip}")).unwrap()),
+            log: wrap!(File::create(format!("../logs/node_{my_ip}")).unwrap()),
             pool: ProcessPool::default(),
             my_ip,
             interface_ip,
@@ -118,24 +119,61 @@ impl NodesHeart {
         current_summaries: &mut Summaries,
     ) {
         let NodeProcessOutput {
-            share_set,
+            share_sets,
+            refresh,
             result,
             summaries,
+            private_share,
+            crashed,
+            rounds,
         } = (*output).clone();
-        if let Some(share_set) = share_set {
+        let public_keys = Self::public_keys(&share_sets);
+        for share_set in share_sets {
             let ident = share_set.identity();
             if !set_already_saved.contains(&ident) {
                 self.save_share_set(share_set).await;
                 set_already_saved.insert(ident);
             }
         }
+        if let Some(refresh) = refresh {
+            self.refresh_share_sets(refresh).await;
+        }
         current_summaries.add_assign(summaries);
 
-        let mut msg = vec![InterfaceCode::Output.into()];
-        enc!(result, msg);
+        let msg = match private_share {
+            _ if crashed => vec![InterfaceCode::Crashed.into()],
+            Some(shares) => {
+                let mut msg = vec![InterfaceCode::PrivateShare.into()];
+                msg.extend(shares);
+                msg
+            }
+            None if !public_keys.is_empty() => {
+                let mut msg = vec![InterfaceCode::PublicKeys.into()];
+                enc!((result, rounds, public_keys), msg);
+                msg
+            }
+            None => {
+                let mut msg = vec![InterfaceCode::Output.into()];
+                enc!((result, rounds), msg);
+                msg
+            }
+        };
         self.contact_interface(msg, id);
     }
 
+    /// Public keys of the Feldman sets by session, the interface encrypts the inputs of the
+    /// decryptions to them
+    fn public_keys(share_sets: &[CryptoSet]) -> Vec<(u8, Vec<G1Projective>)> {
+        share_sets
+            .iter()
+            .filter(|set| matches!(set.identity().2, Algo::Feldman | Algo::Adkg) && set.has_comm())
+            .map(|set| {
+                let keys = set.get_comm().all().iter().map(|line| line[0]).collect();
+                (set.identity().3, keys)
+            })
+            .collect()
+    }
+
     async fn send_summaries(&self, summ: &mut Summaries) {
         log!(self, "Sending summaries: {summ:?}");
         let summaries = summ.get_messages();
@@ -221,13 +259,13 @@ impl NodesHeart {
         .await;
     }
 
-    async fn handle_message(self, mut bytes: Vec<u8>, id: OpId) {
-        match bytes[0].into() {
-            NameSpace::Heart => {
+    async fn handle_message(self, mut bytes: Vec<u8>, sender: NodeId, id: OpId) {
+        match bytes.first().copied().and_then(NameSpace::from_checked) {
+            Some(NameSpace::Heart) => {
                 bytes.remove(0);
                 self.heart_command(bytes, id).await
             }
-            _ => self.send_bytes(bytes, id),
+            _ => self.send_bytes((sender, bytes), id),
         }
     }
 
@@ -267,7 +305,9 @@ impl NodesHeart {
                             check_summ(&self, &mut summaries);
                         }
                         let cloned_self = self.clone();
-                        tokio::spawn(async move { cloned_self.handle_message(bytes, id).await });
+                        tokio::spawn(
+                            async move { cloned_self.handle_message(bytes, sender, id).await },
+                        );
                     }
                     HeartMessage::EmitSumm(s) => {
                         log!(self, "Message Listener: New summary received ! {s:?} !");
@@ -300,7 +340,10 @@ impl NodesHeart {
         log!(
             self,
             "New command: {:?}",
-            NodeCommand::from(bytes_message[0])
+            bytes_message
+                .first()
+                .copied()
+                .and_then(NodeCommand::from_checked)
         );
         select!(
             self_select, NodeCommand, bytes_message, self,
@@ -313,45 +356,74 @@ impl NodesHeart {
         );
     }
 
-    fn send_bytes(self, bytes: Vec<u8>, id: OpId) {
+    fn send_bytes(self, message: Message, id: OpId) {
         spawn(async move {
-            let _ = self.pool.wait_and_send(id, bytes).await;
+            let _ = self.pool.wait_and_send(id, message).await;
         });
     }
 
     async fn new_process(&self, bytes: &[u8], id: OpId) {
         log!(self, "New process: {id}");
         let fields: Fields = dec!(bytes, Fields);
-        let set_identity = (fields.n(), fields.t(), fields.algo());
-        let share_set = match fields.step() {
-            Step::Sharing => CryptoSet::new(set_identity),
-            Step::Reconstruct => self.get_share_set(set_identity).await,
+        let n = fields.nodes_involved();
+        let dealers = match fields.agrees_on_sessions() {
+            true => n,
+            false => fields.dealers(),
         };
-
-        let n = fields.n();
+        // The agreement on the sessions runs in a session of its own, following the dealers' ones
+        let sessions = dealers + fields.agrees_on_sessions() as u16;
+        assert!(dealers <= n, "More dealers than nodes");
+        assert!(
+            sessions <= u8::MAX as u16,
+            "More sessions than a session tag can hold"
+        );
         self.send_message(HeartMessage::EmitN(n as usize)).await;
 
-        let network = {
+        let networks = {
             let mut network = self.network.lock().await;
             network.switch_on(global_lib::KindEvaluation::Debit);
             network.adjust(n as usize).await;
-            network.extract_subnetwork(n as usize)
+            (0..sessions)
+                .map(|_| network.extract_subnetwork(n as usize))
+                .collect::<Vec<_>>()
         };
         let public_keys = self.public_keys.lock().await[..n as usize].to_vec();
+        let keypair = self.keypair.lock().await.as_ref().unwrap().clone();
+        // Only the source committee of a resharing runs the agreements
+        let coin_set = match fields.uses_aba() && self.index() < fields.n() {
+            true => Some(
+                self.get_share_set((fields.n(), fields.t(), Algo::Feldman, 0))
+                    .await,
+            ),
+            false => None,
+        };
 
-        let input = NodeProcessInput::new(
-            fields,
-            id,
-            self.index(),
-            self.keypair.lock().await.as_ref().unwrap().clone(),
-            network,
-            public_keys,
-            share_set,
-            id as u16 % n,
-            *self.base.as_ref().unwrap(),
-        );
+        let mut inputs = Vec::with_capacity(sessions as usize);
+        for (session, network) in (0..sessions).zip(networks) {
+            let set_identity = (fields.n(), fields.t(), fields.algo(), session as u8);
+            let share_set = match fields.step() {
+                Step::Sharing | Step::Beacon | Step::Refresh => CryptoSet::new(set_identity),
+                Step::Reshare if self.index() >= fields.n() => CryptoSet::new(set_identity),
+                Step::Reconstruct | Step::Sign | Step::Decrypt | Step::Reshare | Step::Triples => {
+                    self.get_share_set(set_identity).await
+                }
+            };
+            inputs.push(NodeProcessInput::new(
+                fields.clone(),
+                id,
+                self.index(),
+                keypair.clone(),
+                network,
+                public_keys.clone(),
+                share_set,
+                (id as u16 + session) % fields.n(),
+                *self.base.as_ref().unwrap(),
+                (sessions > 1).then_some(session as u8),
+                coin_set.clone(),
+            ));
+        }
         self.pool
-            .new_task::<Node, NodeProcessInput>(id, input)
+            .new_task::<Node, Vec<NodeProcessInput>>(id, inputs)
             .await
             .unwrap();
     }
@@ -504,7 +576,7 @@ impl NodesHeart {
             .iter()
             .position(|addr| *addr == self.my_ip)
             .expect("Im not in the network") as u16;
-        *self.log.lock().await = File::create(&format!("../logs/node_{index}")).unwrap();
+        *self.log.lock().await = File::create(format!("../logs/node_{index}")).unwrap();
         let kp = KeyPair::generate(&base, &mut thread_rng());
         let pk = kp.extract_public_key();
         let _ = self.keypair.lock().await.insert(kp);
@@ -595,6 +667,25 @@ impl NodesHeart {
         self.shares_map.lock().await.insert(set.identity(), set);
     }
 
+    /// Adds the zero sharing to the sets of every session of the refreshed sharing
+    async fn refresh_share_sets(&self, refresh: CryptoSet) {
+        let (n, t, algo, _) = refresh.identity();
+        let mut refreshed = false;
+        for (ident, set) in self.shares_map.lock().await.iter_mut() {
+            if (ident.0, ident.1, ident.2) == (n, t, algo) {
+                *set += &refresh;
+                refreshed = true;
+            }
+        }
+        if !refreshed {
+            panic!(
+                "Node {} has no set to refresh for the size {:?}",
+                self.index(),
+                (n, t, algo)
+            )
+        }
+    }
+
     async fn get_share_set(&self, ident: CryptoSetIdentity) -> CryptoSet {
         match self.shares_map.lock().await.get(&ident) {
             Some(set) => set.clone(),
@@ -119,7 +119,7 @@ impl Summaries {
             Some(summ) => summ,
             None => return,
         };
-        if summ.awaited.is_some() && summ.received >= *summ.awaited.as_ref().unwrap() {
+        if summ.awaited.is_some_and(|awaited| summ.received >= awaited) {
             if summ.awaited > Some(summ.received) {
                 eprintln!(
                     "NODE {} received to much message from {index}, received {}, when {} were awaited",
@@ -0,0 +1,231 @@
+use crate::{
+    acs::vote,
+    break_if_over,
+    broadcast::broadcast_message_types::BroadcastMessageType,
+    crypto::{
+        combine_reshare, compute_feldman_comm_and_shares_of, crypto_set::CryptoSet,
+        encrypt_sub_shares, feldman_verify, product_proofs, triple_halves, verify_product,
+        Commitment, DleqProof, EncSubShares, Share, SubShareComplaint,
+    },
+    log,
+    node::{configuration::Configuration, node::Node, node_message::NodeMessage},
+    sub_shares::{SubShares, SubSharesAction},
+};
+use blstrs::Scalar;
+use ff::Field;
+use global_lib::{
+    enc,
+    messages::{NameSpace, TriplesCommand},
+    try_dec, Wrapped,
+};
+use notifier_hub::notifier::MessageReceiver as Receiver;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    sync::Arc,
+};
+
+type Dealing = (Commitment, Vec<DleqProof>, EncSubShares);
+
+/// Beaver triples from the two halves `a` and `b` of the shared batch: every node deals a Feldman
+/// sharing of its products `a_i * b_i` with a proof of multiplication, the sub-shares being
+/// encrypted in the broadcast dealing. A node acknowledges the dealings whose sub-share is valid
+/// and complains with a proof on the others. The nodes agree through an ACS on the valid dealings
+/// that n - t nodes acknowledged, recover their sub-shares of the faulty dealers from the others,
+/// and reduce 2t + 1 agreed dealings to a share of `a * b`.
+pub async fn triples(node: Wrapped<Node>) {
+    let channels = [
+        NodeMessage::TriplesSenderConst,
+        NodeMessage::BroadcastTriplesConst,
+        NodeMessage::AcsOutputConst,
+    ];
+    let receiver = node.lock().await.subscribe_multiple(&channels);
+    Node::start_subprotocols(&node, vec![NameSpace::Aba, NameSpace::Acs]).await;
+    let cloned_node = node.clone();
+    tokio::spawn(async move { triples_receiver(cloned_node, receiver).await });
+    let mut node = node.lock().await;
+    if node.is_byz() {
+        return;
+    }
+    let (a, b) = triple_halves(node.config(), node.my_share());
+    let products = a.iter().zip(b).map(|(a, b)| a * b).collect::<Vec<_>>();
+    let (comm, sub_shares) = compute_feldman_comm_and_shares_of(node.config(), &products);
+    let proofs = product_proofs(node.config(), node.get_comm(), node.my_share());
+    let pkeys = (0..node.n())
+        .map(|i| node.get_specific_key(i).c_key())
+        .collect::<Vec<_>>();
+    let enc = encrypt_sub_shares(node.config(), &pkeys, &sub_shares);
+    let dealing: Dealing = (comm, proofs, enc);
+    node.reliable_broadcast(BroadcastMessageType::Triples, enc!(dealing))
+        .await;
+}
+
+async fn triples_receiver(node: Wrapped<Node>, mut receiver: Receiver<NodeMessage>) {
+    let (n, t, mut sub_shares) = {
+        let node = node.lock().await;
+        let pkeys = (0..node.n())
+            .map(|i| node.get_specific_key(i).c_key())
+            .collect();
+        let sub_shares = SubShares::new(
+            node.config().clone(),
+            node.index(),
+            *node.my_decrypt_skey(),
+            pkeys,
+        );
+        (node.n(), node.t(), sub_shares)
+    };
+    let enough = 2 * t as usize + 1;
+    let enough_acks = (n - t) as usize;
+    let mut dealings = HashMap::<u16, Commitment>::new();
+    let mut acks = HashMap::<u16, HashSet<u16>>::new();
+    let mut voted = HashSet::<u16>::new();
+    let mut agreed: Option<Vec<u16>> = None;
+    loop {
+        let msg = break_if_over!(receiver);
+        let mut actions = Vec::new();
+        match msg {
+            NodeMessage::TriplesSender(sender, bytes) => match bytes
+                .first()
+                .copied()
+                .and_then(TriplesCommand::from_checked)
+            {
+                _ if sender >= n => continue,
+                Some(TriplesCommand::Ack) => {
+                    let dealer: u16 = try_dec!(bytes[1..], continue);
+                    acks.entry(dealer).or_default().insert(sender);
+                }
+                Some(TriplesCommand::Complaint) => {
+                    let complaint: SubShareComplaint = try_dec!(bytes[1..], continue);
+                    actions.extend(sub_shares.new_complaint(sender, complaint));
+                }
+                Some(TriplesCommand::Release) => {
+                    let (dealer, share): (u16, Share) = try_dec!(bytes[1..], continue);
+                    if sub_shares.new_release(sender, dealer, share) {
+                        log!(node, "TRIPLES: recovered the sub-share of {dealer}");
+                    }
+                }
+                None => continue,
+            },
+            NodeMessage::BroadcastTriples(i, bytes) => {
+                if i >= n || dealings.contains_key(&i) {
+                    continue;
+                }
+                let (dealing, proofs, enc): Dealing = try_dec!(bytes, continue);
+                let valid = {
+                    let node = node.lock().await;
+                    verify_product(node.config(), node.get_comm(), i, &dealing, &proofs)
+                };
+                if !valid {
+                    log!(node, "TRIPLES: invalid multiplication from {i}");
+                    continue;
+                }
+                actions = sub_shares.new_dealing(i, dealing.clone(), enc);
+                dealings.insert(i, dealing);
+            }
+            NodeMessage::AcsOutput(subset) => {
+                let mut set = subset.into_iter().map(|(i, _)| i).collect::<Vec<_>>();
+                log!(node, "TRIPLES: agreed on the dealings of {set:?}");
+                set.truncate(enough);
+                agreed = Some(set);
+            }
+            _ => panic!("Unexpected message"),
+        }
+        send_actions(&node, actions).await;
+        // A dealing is voted once enough nodes got a valid sub-share from it, so that the others
+        // can recover theirs
+        let ready = dealings
+            .keys()
+            .filter(|i| !voted.contains(*i) && acks.get(*i).map_or(0, |a| a.len()) >= enough_acks)
+            .copied()
+            .collect::<Vec<_>>();
+        for i in ready {
+            vote(&node, i).await;
+            voted.insert(i);
+        }
+        let set = match &agreed {
+            Some(set) => set,
+            None => continue,
+        };
+        if !set
+            .iter()
+            .all(|i| dealings.contains_key(i) && sub_shares.get(*i).is_some())
+        {
+            continue;
+        }
+        let mut node_guard = node.lock().await;
+        let config = node_guard.config();
+        let (comm, share) = combine_reshare(config, config, set, &dealings, sub_shares.all());
+        if !feldman_verify(config, &comm, &share) {
+            node_guard.log("TRIPLES: the product share doesn't match its commitment");
+            drop(node_guard);
+            Node::abort(node);
+            return;
+        }
+        let triples = triple_set(config, node_guard.set(), comm, share);
+        *node_guard.set_mut() = triples;
+        drop(node_guard);
+        Node::output(node);
+        return;
+    }
+}
+
+/// Acknowledges the valid sub-shares and complains about the invalid ones to every node, and
+/// releases the sub-shares of the faulty dealers to the complainers
+async fn send_actions(node: &Wrapped<Node>, actions: Vec<SubSharesAction>) {
+    let mut node = node.lock().await;
+    for action in actions {
+        match action {
+            SubSharesAction::Ack(dealer) => {
+                let msg = enc!(Triples, TriplesCommand::Ack, dealer);
+                node.broadcast(msg, true).await;
+            }
+            SubSharesAction::Complain(complaint) => {
+                node.log(&format!("TRIPLES: invalid sub-share from {}", complaint.0));
+                let msg = enc!(Triples, TriplesCommand::Complaint, *complaint);
+                node.broadcast(msg, true).await;
+            }
+            SubSharesAction::Release(to, dealer, share) => {
+                let msg = enc!(Triples, TriplesCommand::Release, (dealer, *share));
+                node.contact(to as usize, Arc::new(msg));
+            }
+        }
+    }
+}
+
+/// The triples as the set of the sharing they are made from, whose batch holds the `a`, then the
+/// `b`, then the `ab`, each committed by its Feldman line. The set keeps the session of the sharing
+/// so that the next operations on it use the triples, a new operation replacing the products.
+fn triple_set(
+    config: &Configuration,
+    set: &CryptoSet,
+    products_comm: Commitment,
+    products: Share,
+) -> CryptoSet {
+    let index = products.index();
+    let (a, b) = triple_halves(config, set.get(index));
+    let half = a.len();
+    let mut comm = Commitment::new(*set.get_comm().base());
+    for line in set.get_comm().all()[..2 * half]
+        .iter()
+        .chain(products_comm.all())
+    {
+        comm.push_line(line.clone());
+    }
+    let batch = a
+        .iter()
+        .chain(b)
+        .chain(products.only_share().batch())
+        .copied()
+        .collect::<Vec<_>>();
+    let rands = vec![Scalar::zero(); batch.len()];
+    let mut triples = CryptoSet::new(set.identity());
+    triples.set_comm(comm);
+    triples.new_share(Share::new(index, batch, rands));
+    // The dealer of the sharing checks the reconstructions of the triples as the ones of its secrets
+    if let Some(secrets) = set.get_secrets() {
+        let (a, b) = secrets[..2 * half].split_at(half);
+        let products = a.iter().zip(b).map(|(a, b)| a * b);
+        triples.set_secrets(a.iter().chain(b).copied().chain(products).collect());
+    }
+    triples
+}